@@ -1,9 +1,111 @@
-fn main() {
-    use win32_version_info::VersionInfo;
-    let file_name =
-        std::env::args()
-            .nth(1)
-            .expect("file name not provided");
-    let info = VersionInfo::from_file(file_name);
-    println!("{:#?}", info);
+//! Prints the version information of a file.
+//!
+//! ```text
+//! cargo run --example main -- [--json | --field <name> | --fixed | --all-translations] <file>
+//! ```
+//!
+//! - `--json` prints the version information as JSON, which requires the
+//!   `serde` feature.
+//! - `--field <name>` prints a single string field, e.g. `--field CompanyName`.
+//! - `--fixed` prints the fixed, numeric part of the version information.
+//! - `--all-translations` prints the string fields of every translation.
+//!
+//! Without any flag, the whole version information is pretty-printed.
+
+#[cfg(windows)]
+fn main() -> std::process::ExitCode {
+    use std::process::ExitCode;
+    use win32_version_info::{
+        ErrorKind,
+        VersionInfo,
+        VersionInfoOs,
+        STANDARD_FIELDS,
+    };
+
+    enum Format {
+        AllTranslations,
+        Debug,
+        Field(String),
+        Fixed,
+        Json,
+    }
+
+    let mut args = std::env::args().skip(1);
+    let mut format = Format::Debug;
+    let mut file_name = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => format = Format::Json,
+            "--fixed" => format = Format::Fixed,
+            "--all-translations" => format = Format::AllTranslations,
+            "--field" => {
+                let Some(name) = args.next() else {
+                    eprintln!("error: `--field` requires a field name");
+                    return ExitCode::FAILURE;
+                };
+                format = Format::Field(name);
+            }
+            _ => file_name = Some(arg),
+        }
+    }
+    let Some(file_name) = file_name else {
+        eprintln!("usage: main [--json | --field <name> | --fixed | --all-translations] <file>");
+        return ExitCode::FAILURE;
+    };
+
+    let info = match VersionInfo::from_file(&file_name) {
+        Ok(info) => info,
+        Err(err) if matches!(err.kind(), ErrorKind::NoVersionInfo) => {
+            eprintln!("error: {file_name} has no version information");
+            return ExitCode::FAILURE;
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match format {
+        Format::Debug => println!("{info:#?}"),
+        #[cfg(feature = "serde")]
+        Format::Json => println!("{}",
+            serde_json::to_string_pretty(&info).expect("failed to serialize to JSON")),
+        #[cfg(not(feature = "serde"))]
+        Format::Json => {
+            eprintln!("error: `--json` requires the `serde` feature");
+            return ExitCode::FAILURE;
+        }
+        Format::Field(name) => {
+            let Some((_, value)) = info
+                .fields()
+                .find(|(field, _)| field.eq_ignore_ascii_case(&name)) else {
+                eprintln!("error: unknown field `{name}`, expected one of {STANDARD_FIELDS:?}");
+                return ExitCode::FAILURE;
+            };
+            println!("{value}");
+        }
+        Format::Fixed => {
+            let Some(fixed_file_info) = info.fixed_file_info else {
+                eprintln!("error: {file_name} has no fixed version information");
+                return ExitCode::FAILURE;
+            };
+            println!("{fixed_file_info:#?}");
+        }
+        Format::AllTranslations => {
+            let infos = match VersionInfoOs::all_translations_fields(&file_name) {
+                Ok(infos) => infos,
+                Err(err) => {
+                    eprintln!("error: {err}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            for (translation, info) in infos {
+                println!("{translation}: {info:#?}");
+            }
+        }
+    }
+    ExitCode::SUCCESS
 }
+
+#[cfg(not(windows))]
+const fn main() {}
@@ -0,0 +1,32 @@
+//! Feeds arbitrary bytes to the parsers of raw version information blocks and
+//! queries everything a block exposes, as the values are located by walking
+//! the block by the untrusted lengths stored in it.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use win32_version_info::{
+    VersionInfo,
+    VersionInfoBlock,
+    VersionInfoOs,
+    VsFixedFileInfo,
+    STANDARD_FIELDS,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = VersionInfo::from_bytes(data);
+    let _ = VersionInfoOs::from_bytes(data);
+    let _ = VsFixedFileInfo::from_bytes(data);
+
+    let Ok(block) = VersionInfoBlock::from_bytes(data) else {
+        return;
+    };
+    let _ = VersionInfoOs::from_block(&block);
+    let _ = block.query_raw("\\");
+    let _ = block.query_raw("\\VarFileInfo\\Translation");
+    for translation in block.translations().into_iter().flatten() {
+        for name in STANDARD_FIELDS {
+            let _ = block.query_raw(&format!("\\StringFileInfo\\{translation}\\{name}"));
+        }
+    }
+});
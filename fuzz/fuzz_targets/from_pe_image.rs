@@ -0,0 +1,15 @@
+//! Feeds arbitrary bytes to the parser of PE images, which locates the
+//! version resource by following the untrusted offsets in the headers and the
+//! resource table.
+
+#![no_main]
+
+use std::io::Cursor;
+
+use libfuzzer_sys::fuzz_target;
+use win32_version_info::VersionInfoOs;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = VersionInfoOs::from_pe_image(data);
+    let _ = VersionInfoOs::from_pe_reader(Cursor::new(data));
+});
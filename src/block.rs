@@ -0,0 +1,228 @@
+//! Validation of raw version information blocks.
+//!
+//! A version information block is a tree of nodes, each laid out as follows,
+//! with the value and every child aligned to 32 bits from the start of the
+//! block:
+//!
+//! - `wLength`, the length of the node in bytes, including its children.
+//! - `wValueLength`, the length of the value, in words for text values and in
+//!   bytes for binary ones.
+//! - `wType`, `1` for text values and `0` for binary ones.
+//! - `szKey`, the NUL-terminated UTF-16 key of the node.
+//! - The value, followed by the child nodes.
+//!
+//! All integers are little-endian.
+
+use crate::{
+    FixedFileInfo,
+    Translation,
+    VsFixedFileInfo,
+};
+
+/// The key of the root node of every version information block.
+const ROOT_KEY: &str = "VS_VERSION_INFO";
+
+/// The size of the fixed `wLength`, `wValueLength` and `wType` header.
+const HEADER_SIZE: usize = 6;
+
+/// The maximum depth of nodes accepted, far deeper than any real block, which
+/// is at most four levels deep.
+const MAX_DEPTH: usize = 16;
+
+/// Checks that `data` starts with a `VS_VERSION_INFO` root node that lies
+/// within `data`, and that every descendant node lies within its parent.
+///
+/// This guarantees that walking the tree by node lengths, as `VerQueryValueW`
+/// does, never reads out of bounds.
+pub fn validate(data: &[u8]) -> bool {
+    let root_key = ROOT_KEY.encode_utf16().chain(Some(0));
+    validate_node(data, 0, data.len(), 0)
+        && root_key
+            .enumerate()
+            .all(|(index, unit)| read_u16(data, HEADER_SIZE + index * 2) == Some(unit))
+}
+
+/// Returns the value of the root node of a block, i.e. its
+/// `VS_FIXEDFILEINFO`, or `None` if the root node is truncated or its value
+/// overruns it.
+///
+/// The value starts at the first 32-bit aligned offset after the root key,
+/// relative to the start of the block rather than in memory, i.e. offset 40
+/// after `VS_VERSION_INFO`. Computing it here rather than with
+/// `VerQueryValueW`, which aligns absolute addresses, keeps it correct however
+/// the block is aligned in memory, e.g. when copied out of a PE image.
+pub fn root_value(data: &[u8]) -> Option<&[u8]> {
+    let node = data.get(..usize::from(read_u16(data, 0)?))?;
+    let value_len = usize::from(read_u16(node, 2)?);
+    let key_len = node
+        .get(HEADER_SIZE..)?
+        .chunks_exact(2)
+        .position(|unit| unit == [0, 0])?;
+    let value_start = align(HEADER_SIZE + (key_len + 1) * 2);
+    node.get(value_start..value_start.checked_add(value_len)?)
+}
+
+/// Describes the tree of nodes of a block as text, one node per line indented
+/// by its depth, with the length of its value in bytes and the value itself,
+/// decoded for the `VS_FIXEDFILEINFO` of the root node and for translations.
+///
+/// Unlike [`validate`], this doesn't require a well-formed block: a node that
+/// doesn't lie within its parent is described as malformed and its following
+/// siblings are skipped, so that as much of the block as possible is
+/// described.
+pub fn dump(data: &[u8]) -> String {
+    let mut lines = Vec::new();
+    dump_node(data, 0, data.len(), 0, &[], &mut lines);
+    lines.iter().flat_map(|line| [line.as_str(), "\n"]).collect()
+}
+
+/// Describes the node at `start` and its children, returning the length of
+/// the node or `None` if it is malformed.
+fn dump_node(
+    data: &[u8],
+    start: usize,
+    end: usize,
+    depth: usize,
+    parent_key: &[u16],
+    lines: &mut Vec<String>) -> Option<usize> {
+    let indent = "  ".repeat(depth);
+    let node = read_u16(data, start)
+        .map(usize::from)
+        .filter(|&len| len >= HEADER_SIZE && start + len <= end && depth <= MAX_DEPTH)
+        .and_then(|len| data.get(start..start + len));
+    let Some(node) = node else {
+        lines.push(format!("{indent}<malformed node at offset {start}>"));
+        return None;
+    };
+    let Some(key_len) = node[HEADER_SIZE..].chunks_exact(2).position(|unit| unit == [0, 0]) else {
+        lines.push(format!("{indent}<malformed node at offset {start}, with an unterminated key>"));
+        return None;
+    };
+    let key = to_units(&node[HEADER_SIZE..HEADER_SIZE + key_len * 2]);
+
+    let value_len = usize::from(read_u16(node, 2)?);
+    let text = read_u16(node, 4)? == 1;
+    let value_size = if text { value_len * 2 } else { value_len };
+    let value_start = align(HEADER_SIZE + (key_len + 1) * 2);
+    let value = node.get(value_start..).unwrap_or_default();
+    let overrun = value_size.saturating_sub(value.len());
+    let value = &value[..value_size.min(value.len())];
+
+    let fixed_file_info = (depth == 0).then(|| VsFixedFileInfo::parse(value)).flatten();
+    let description = if value.is_empty() {
+        String::new()
+    } else if text {
+        format!("{:?}", String::from_utf16_lossy(&to_units(value)))
+    } else if fixed_file_info.is_some() {
+        "VS_FIXEDFILEINFO".to_owned()
+    } else if key.iter().copied().eq("Translation".encode_utf16())
+        && parent_key.iter().copied().eq("VarFileInfo".encode_utf16()) {
+        value
+            .chunks_exact(4)
+            .filter_map(|raw| raw.try_into().ok().map(Translation::from_le_bytes))
+            .map(|translation| translation.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    } else {
+        value.iter().map(|byte| format!("{byte:02x}")).collect::<Vec<_>>().join(" ")
+    };
+    lines.push(format!(
+        "{indent}{} ({value_size} bytes, {}{}){}{description}",
+        String::from_utf16_lossy(&key),
+        if text { "text" } else { "binary" },
+        if overrun > 0 { format!(", overrunning the node by {overrun} bytes") } else { String::new() },
+        if description.is_empty() { "" } else { ": " }));
+
+    if let Some(raw) = fixed_file_info {
+        let fixed = FixedFileInfo::from(raw);
+        lines.extend([
+            format!("{indent}  dwSignature: {:#010x}", raw.signature),
+            format!("{indent}  dwStrucVersion: {:#010x}", raw.struc_version),
+            format!("{indent}  dwFileVersion: {}", fixed.file_version),
+            format!("{indent}  dwProductVersion: {}", fixed.product_version),
+            format!("{indent}  dwFileFlagsMask: {:#010x}", fixed.file_flags_mask),
+            format!("{indent}  dwFileFlags: {:#010x}", fixed.file_flags),
+            format!("{indent}  dwFileOS: {:#010x}", fixed.file_os),
+            format!("{indent}  dwFileType: {:#010x}", fixed.file_type),
+            format!("{indent}  dwFileSubtype: {:#010x}", fixed.file_subtype),
+            format!("{indent}  dwFileDate: {:#018x}", fixed.file_date),
+        ]);
+    }
+
+    let mut offset = align(value_start + value_size);
+    while offset + HEADER_SIZE <= node.len() {
+        // zero padding after the last child
+        if read_u16(node, offset) == Some(0) {
+            break;
+        }
+        match dump_node(data, start + offset, start + node.len(), depth + 1, &key, lines) {
+            Some(child_len) => offset = align(offset + child_len),
+            None => break,
+        }
+    }
+    Some(node.len())
+}
+
+/// Reads little-endian UTF-16 code units, ignoring a trailing odd byte.
+fn to_units(bytes: &[u8]) -> Vec<u16> {
+    bytes
+        .chunks_exact(2)
+        .filter_map(|unit| unit.try_into().ok().map(u16::from_le_bytes))
+        .collect()
+}
+
+fn validate_node(data: &[u8], start: usize, end: usize, depth: usize) -> bool {
+    let Some(len) = read_u16(data, start) else {
+        return false;
+    };
+    let node_end = start + usize::from(len);
+    if usize::from(len) < HEADER_SIZE || node_end > end || depth > MAX_DEPTH {
+        return false;
+    }
+
+    let Some(key_len) = data[start + HEADER_SIZE..node_end]
+        .chunks_exact(2)
+        .position(|unit| unit == [0, 0]) else {
+        return false;
+    };
+    let value_start = align(start + HEADER_SIZE + (key_len + 1) * 2);
+
+    let Some(value_len) = read_u16(data, start + 2).map(usize::from) else {
+        return false;
+    };
+    let value_size = if read_u16(data, start + 4) == Some(1) {
+        value_len * 2
+    } else {
+        value_len
+    };
+
+    // a value overrunning its node leaves no room for children, which isn't
+    // an error by itself: the value is bounds-checked separately when read
+    let mut offset = align(value_start + value_size);
+    while offset + HEADER_SIZE <= node_end {
+        match read_u16(data, offset) {
+            // zero padding after the last child
+            Some(0) => break,
+            Some(child_len) => {
+                if !validate_node(data, offset, node_end, depth + 1) {
+                    return false;
+                }
+                offset = align(offset + usize::from(child_len));
+            }
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Rounds `offset` up to a multiple of 4.
+const fn align(offset: usize) -> usize {
+    (offset + 3) & !3
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset.checked_add(2)?)?
+        .try_into()
+        .ok()
+        .map(u16::from_le_bytes)
+}
@@ -0,0 +1,203 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io,
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::{
+        Mutex,
+        MutexGuard,
+        PoisonError,
+    },
+    time::SystemTime,
+};
+
+use crate::{
+    Error,
+    ErrorKind,
+    Result,
+    VersionInfo,
+    VersionInfoBuilder,
+};
+
+/// The number of files whose version information a [`CachedReader`] keeps by
+/// default.
+const DEFAULT_CAPACITY: usize = 4096;
+
+/// What a cached entry was read from, which must still match the file for
+/// the entry to be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stamp {
+    len: u64,
+    modified: SystemTime,
+}
+
+#[derive(Debug)]
+struct Entry {
+    info: VersionInfo,
+    stamp: Stamp,
+    /// The value of [`Entries::clock`] when the entry was last used.
+    used: u64,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    clock: u64,
+    map: HashMap<PathBuf, Entry>,
+}
+
+/// Retrieves version information from files, caching it in memory to skip
+/// reading files again that are unchanged, e.g. when repeatedly scanning a
+/// directory.
+///
+/// Entries are keyed by the canonical path of the file, as resolved by
+/// [`std::fs::canonicalize`], so different paths to the same file share an
+/// entry. An entry is only used while the last modification time and the size
+/// of the file are the same as when it was read, and is read again
+/// otherwise. Errors are never cached.
+///
+/// When the cache is full, the least recently used entry is evicted. The
+/// reader can be shared between threads, but files are read without holding
+/// the cache locked, so threads reading the same uncached file concurrently
+/// may all read it.
+///
+/// This type requires the `cache` feature.
+///
+/// # Examples
+///
+/// ```no_run
+/// use win32_version_info::CachedReader;
+///
+/// let reader = CachedReader::new();
+/// let info = reader.read("path/to/your/file.exe")
+///     .expect("Failed to retrieve version information");
+/// // unless the file has changed since, this doesn't read it again
+/// let again = reader.read("path/to/your/file.exe")
+///     .expect("Failed to retrieve version information");
+/// assert_eq!(info, again);
+/// ```
+#[derive(Debug)]
+pub struct CachedReader {
+    builder: VersionInfoBuilder,
+    capacity: usize,
+    entries: Mutex<Entries>,
+}
+
+impl CachedReader {
+    /// Sets the maximum number of files whose version information is kept.
+    ///
+    /// Defaults to `4096`. `0` disables caching.
+    #[must_use]
+    pub const fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Removes all cached version information.
+    pub fn clear(&self) {
+        self.lock().map.clear();
+    }
+
+    /// Checks whether no version information is cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().map.is_empty()
+    }
+
+    /// Returns the number of files whose version information is cached.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().map.len()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Entries> {
+        // the entries are consistent even if a thread panicked while holding
+        // the lock, as they are only modified by infallible operations
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Creates an empty cache reading files with the default options.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_builder(VersionInfoBuilder::new())
+    }
+
+    /// Retrieves version information from the specified file, or returns the
+    /// cached version information if the file is unchanged since it was last
+    /// read.
+    ///
+    /// See [`VersionInfoBuilder::read`] for details.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    pub fn read<P: AsRef<Path>>(&self, file_name: P) -> Result<VersionInfo> {
+        let file_name = file_name.as_ref();
+        let (canonical, stamp) = Self::stamp(file_name)
+            .map_err(|err| Error::new(ErrorKind::from_os(err)).with_path(file_name))?;
+        if let Some(entry) = self.lock().get_mut(&canonical, stamp) {
+            return Ok(entry.info.clone());
+        }
+        let info = self.builder.read(&canonical)?;
+        self.lock().insert(canonical, stamp, info.clone(), self.capacity);
+        Ok(info)
+    }
+
+    fn stamp(file_name: &Path) -> io::Result<(PathBuf, Stamp)> {
+        let canonical = fs::canonicalize(file_name)?;
+        let metadata = fs::metadata(&canonical)?;
+        let stamp = Stamp {
+            modified: metadata.modified()?,
+            len: metadata.len(),
+        };
+        Ok((canonical, stamp))
+    }
+
+    /// Creates an empty cache reading files with the options of `builder`.
+    #[must_use]
+    pub fn with_builder(builder: VersionInfoBuilder) -> Self {
+        Self {
+            builder,
+            capacity: DEFAULT_CAPACITY,
+            entries: Mutex::default(),
+        }
+    }
+}
+
+impl Default for CachedReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Entries {
+    fn get_mut(&mut self, path: &Path, stamp: Stamp) -> Option<&mut Entry> {
+        self.clock += 1;
+        let clock = self.clock;
+        let entry = self.map.get_mut(path).filter(|entry| entry.stamp == stamp)?;
+        entry.used = clock;
+        Some(entry)
+    }
+
+    fn insert(&mut self, path: PathBuf, stamp: Stamp, info: VersionInfo, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        if !self.map.contains_key(&path) && self.map.len() >= capacity {
+            let least_recently_used = self.map
+                .iter()
+                .min_by_key(|&(_, entry)| entry.used)
+                .map(|(path, _)| path.clone());
+            if let Some(least_recently_used) = least_recently_used {
+                self.map.remove(&least_recently_used);
+            }
+        }
+        self.clock += 1;
+        self.map.insert(path, Entry { stamp, info, used: self.clock });
+    }
+}
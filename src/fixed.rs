@@ -0,0 +1,548 @@
+use std::{
+    fmt,
+    mem,
+    ptr,
+};
+
+use crate::{
+    Result,
+    VersionInfoBlock,
+};
+
+/// The signature of a valid `VS_FIXEDFILEINFO`, i.e. its `dwSignature`.
+const SIGNATURE: u32 = 0xFEEF04BD;
+
+/// The file flag marking a private build, i.e. `VS_FF_PRIVATEBUILD`.
+const VS_FF_PRIVATEBUILD: u32 = 0x08;
+
+/// The file flag marking a special build, i.e. `VS_FF_SPECIALBUILD`.
+const VS_FF_SPECIALBUILD: u32 = 0x20;
+
+/// The version numbers recognized as placeholders by
+/// [`FileVersion::is_placeholder`], i.e. `0.0.0.0` and `1.0.0.0`.
+///
+/// These are the defaults of most project templates, so they usually mean
+/// that the version was never set.
+///
+/// To check against a different set, e.g. to also reject `1.0.0.1`, use
+/// [`slice::contains`] on your own list, possibly extending this one.
+pub const PLACEHOLDER_VERSIONS: &[FileVersion] = &[
+    FileVersion::new(0, 0, 0, 0),
+    FileVersion::new(1, 0, 0, 0),
+];
+
+/// Represents the fixed, language-independent part of the version information
+/// of a file, i.e. the `VS_FIXEDFILEINFO` structure.
+///
+/// Unlike the string fields of [`VersionInfo`](crate::VersionInfo), which are
+/// free-form text written by the vendor, the version numbers here are always
+/// binary and thus reliable for comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[expect(
+    clippy::arbitrary_source_item_ordering,
+    reason = "the fields follow `VS_FIXEDFILEINFO`, and the derived `Ord` compares the file version first",
+)]
+pub struct FixedFileInfo {
+    /// The binary version number of the file.
+    pub file_version: FileVersion,
+    /// The binary version number of the product this file is distributed with.
+    pub product_version: FileVersion,
+    /// The bitmask that specifies the valid bits in `file_flags`.
+    pub file_flags_mask: u32,
+    /// The attributes of the file, e.g. `VS_FF_DEBUG`.
+    pub file_flags: u32,
+    /// The operating system for which this file was designed, see
+    /// [`FixedFileInfo::os`] for its decoded halves.
+    pub file_os: u32,
+    /// The general type of the file, e.g. `VFT_APP`.
+    pub file_type: u32,
+    /// The function of the file, whose meaning depends on `file_type`.
+    pub file_subtype: u32,
+    /// The binary creation date and time stamp of the file, which is zero for
+    /// almost all files.
+    pub file_date: u64,
+}
+
+impl FixedFileInfo {
+    /// Returns the parts of [`FixedFileInfo::file_version`] as
+    /// `[major, minor, build, revision]`.
+    #[must_use]
+    pub const fn file_version_parts(&self) -> [u16; 4] {
+        self.file_version.to_array()
+    }
+
+    /// Checks whether the file is a private build, i.e. whether
+    /// `VS_FF_PRIVATEBUILD` is set in the valid bits of `file_flags`.
+    #[must_use]
+    pub const fn is_private_build(&self) -> bool {
+        self.file_flags & self.file_flags_mask & VS_FF_PRIVATEBUILD != 0
+    }
+
+    /// Checks whether the file is a special build, i.e. whether
+    /// `VS_FF_SPECIALBUILD` is set in the valid bits of `file_flags`.
+    #[must_use]
+    pub const fn is_special_build(&self) -> bool {
+        self.file_flags & self.file_flags_mask & VS_FF_SPECIALBUILD != 0
+    }
+
+    /// Returns [`FixedFileInfo::file_os`], i.e. the operating system and the
+    /// subsystem for which this file was designed.
+    #[must_use]
+    pub const fn os(&self) -> FileOs {
+        FileOs::from_raw(self.file_os)
+    }
+
+    /// Returns the parts of [`FixedFileInfo::product_version`] as
+    /// `[major, minor, build, revision]`.
+    #[must_use]
+    pub const fn product_version_parts(&self) -> [u16; 4] {
+        self.product_version.to_array()
+    }
+}
+
+impl From<VsFixedFileInfo> for FixedFileInfo {
+    fn from(raw: VsFixedFileInfo) -> Self {
+        Self {
+            file_version: FileVersion::from_raw(raw.file_version_ms, raw.file_version_ls),
+            product_version: FileVersion::from_raw(raw.product_version_ms, raw.product_version_ls),
+            file_flags_mask: raw.file_flags_mask,
+            file_flags: raw.file_flags,
+            file_os: raw.file_os,
+            file_type: raw.file_type,
+            file_subtype: raw.file_subtype,
+            file_date: (u64::from(raw.file_date_ms) << 32) | u64::from(raw.file_date_ls),
+        }
+    }
+}
+
+/// The operating system for which a file was designed, i.e. the `dwFileOS`
+/// field of `VS_FIXEDFILEINFO`.
+///
+/// `dwFileOS` combines two values, e.g. `VOS_NT_WINDOWS32` is `VOS_NT` in the
+/// high word, the base operating system, and `VOS__WINDOWS32` in the low word,
+/// the subsystem running on it. Their combinations are not validated, as
+/// files may declare any of them.
+///
+/// # Examples
+///
+/// ```
+/// use win32_version_info::{FileOs, FileOsBase, FileOsSubsystem};
+///
+/// let os = FileOs::from_raw(0x0004_0004);
+/// assert_eq!(os.base(), FileOsBase::Nt);
+/// assert_eq!(os.subsystem(), FileOsSubsystem::Windows32);
+///
+/// let os = FileOs::from_raw(0x0003_0003);
+/// assert_eq!(os.base(), FileOsBase::Os232);
+/// assert_eq!(os.subsystem(), FileOsSubsystem::Pm32);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct FileOs(u32);
+
+impl FileOs {
+    /// Returns the base operating system, i.e. the high word.
+    #[must_use]
+    pub const fn base(self) -> FileOsBase {
+        FileOsBase::from_raw((self.0 >> 16) as u16)
+    }
+
+    /// Wraps the raw value of `dwFileOS`.
+    #[must_use]
+    pub const fn from_raw(raw: u32) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the subsystem running on the base operating system, i.e. the
+    /// low word.
+    #[must_use]
+    pub const fn subsystem(self) -> FileOsSubsystem {
+        FileOsSubsystem::from_raw((self.0 & 0xFFFF) as u16)
+    }
+
+    /// Returns the raw value of `dwFileOS`.
+    #[must_use]
+    pub const fn to_raw(self) -> u32 {
+        self.0
+    }
+}
+
+/// The base operating system for which a file was designed, i.e. the high
+/// word of `dwFileOS`, see [`FileOs::base`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FileOsBase {
+    /// MS-DOS, i.e. `VOS_DOS`.
+    Dos,
+    /// Windows NT, i.e. `VOS_NT`.
+    Nt,
+    /// 16-bit OS/2, i.e. `VOS_OS216`.
+    Os216,
+    /// 32-bit OS/2, i.e. `VOS_OS232`.
+    Os232,
+    /// `VOS_UNKNOWN`, i.e. zero, or any other value, with the raw value of
+    /// the high word.
+    Unknown(u16),
+    /// Windows CE, i.e. `VOS_WINCE`.
+    WinCe,
+}
+
+impl FileOsBase {
+    /// Maps the raw value of the high word of `dwFileOS`.
+    #[must_use]
+    pub const fn from_raw(raw: u16) -> Self {
+        match raw {
+            0x0001 => Self::Dos,
+            0x0002 => Self::Os216,
+            0x0003 => Self::Os232,
+            0x0004 => Self::Nt,
+            0x0005 => Self::WinCe,
+            _ => Self::Unknown(raw),
+        }
+    }
+
+    /// Returns the raw value of the high word of `dwFileOS`.
+    #[must_use]
+    pub const fn to_raw(self) -> u16 {
+        match self {
+            Self::Dos => 0x0001,
+            Self::Os216 => 0x0002,
+            Self::Os232 => 0x0003,
+            Self::Nt => 0x0004,
+            Self::WinCe => 0x0005,
+            Self::Unknown(raw) => raw,
+        }
+    }
+}
+
+/// The subsystem for which a file was designed, running on the base operating
+/// system, i.e. the low word of `dwFileOS`, see [`FileOs::subsystem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FileOsSubsystem {
+    /// No subsystem, i.e. `VOS__BASE`, which is zero.
+    Base,
+    /// 16-bit Presentation Manager, i.e. `VOS__PM16`.
+    Pm16,
+    /// 32-bit Presentation Manager, i.e. `VOS__PM32`.
+    Pm32,
+    /// Any other subsystem, with the raw value of the low word.
+    Unknown(u16),
+    /// 16-bit Windows, i.e. `VOS__WINDOWS16`.
+    Windows16,
+    /// 32-bit Windows, i.e. `VOS__WINDOWS32`.
+    Windows32,
+}
+
+impl FileOsSubsystem {
+    /// Maps the raw value of the low word of `dwFileOS`.
+    #[must_use]
+    pub const fn from_raw(raw: u16) -> Self {
+        match raw {
+            0x0000 => Self::Base,
+            0x0001 => Self::Windows16,
+            0x0002 => Self::Pm16,
+            0x0003 => Self::Pm32,
+            0x0004 => Self::Windows32,
+            _ => Self::Unknown(raw),
+        }
+    }
+
+    /// Returns the raw value of the low word of `dwFileOS`.
+    #[must_use]
+    pub const fn to_raw(self) -> u16 {
+        match self {
+            Self::Base => 0x0000,
+            Self::Windows16 => 0x0001,
+            Self::Pm16 => 0x0002,
+            Self::Pm32 => 0x0003,
+            Self::Windows32 => 0x0004,
+            Self::Unknown(raw) => raw,
+        }
+    }
+}
+
+/// The `VS_FIXEDFILEINFO` structure exactly as defined by Win32, for passing
+/// to other APIs or comparing byte-for-byte.
+///
+/// Prefer [`FixedFileInfo`] for anything else, which combines the split
+/// halves of the version numbers and the date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[repr(C)]
+pub struct VsFixedFileInfo {
+    /// `dwSignature`, always `0xFEEF04BD`.
+    pub signature: u32,
+    /// `dwStrucVersion`, the version of this structure, with the major
+    /// version in the high word.
+    pub struc_version: u32,
+    /// `dwFileVersionMS`, the major and minor file version.
+    pub file_version_ms: u32,
+    /// `dwFileVersionLS`, the build and revision file version.
+    pub file_version_ls: u32,
+    /// `dwProductVersionMS`, the major and minor product version.
+    pub product_version_ms: u32,
+    /// `dwProductVersionLS`, the build and revision product version.
+    pub product_version_ls: u32,
+    /// `dwFileFlagsMask`, the valid bits in `file_flags`.
+    pub file_flags_mask: u32,
+    /// `dwFileFlags`, the attributes of the file.
+    pub file_flags: u32,
+    /// `dwFileOS`, the operating system for which this file was designed.
+    pub file_os: u32,
+    /// `dwFileType`, the general type of the file.
+    pub file_type: u32,
+    /// `dwFileSubtype`, the function of the file.
+    pub file_subtype: u32,
+    /// `dwFileDateMS`, the high 32 bits of the creation date and time stamp.
+    pub file_date_ms: u32,
+    /// `dwFileDateLS`, the low 32 bits of the creation date and time stamp.
+    pub file_date_ls: u32,
+}
+
+impl VsFixedFileInfo {
+    /// Reads a copy of the `VS_FIXEDFILEINFO` in the root block `\` of a raw
+    /// version information block, i.e. the data returned by
+    /// `GetFileVersionInfoW` or the content of an `RT_VERSION` resource.
+    ///
+    /// Returns `Ok(None)` if the block has no `VS_FIXEDFILEINFO` or its
+    /// signature doesn't match.
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`ErrorKind::MalformedBlock`](crate::ErrorKind::MalformedBlock)
+    /// if `data` is not a well-formed version information block.
+    pub fn from_bytes(data: &[u8]) -> Result<Option<Self>> {
+        VersionInfoBlock::from_bytes(data)?.get_raw_fixed_file_info()
+    }
+
+    /// Parses a `VS_FIXEDFILEINFO` from its little-endian binary form, which is
+    /// what `VerQueryValueW` returns for the root block `\`.
+    ///
+    /// Returns `None` if `data` is too short or the signature doesn't match.
+    pub(crate) fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < mem::size_of::<Self>() {
+            return None;
+        }
+        // values in the block are only guaranteed to be 32-bit aligned
+        // relative to the start of the block, not in memory
+        let raw = unsafe { ptr::read_unaligned(data.as_ptr().cast::<Self>()) };
+        let raw = Self {
+            signature: u32::from_le(raw.signature),
+            struc_version: u32::from_le(raw.struc_version),
+            file_version_ms: u32::from_le(raw.file_version_ms),
+            file_version_ls: u32::from_le(raw.file_version_ls),
+            product_version_ms: u32::from_le(raw.product_version_ms),
+            product_version_ls: u32::from_le(raw.product_version_ls),
+            file_flags_mask: u32::from_le(raw.file_flags_mask),
+            file_flags: u32::from_le(raw.file_flags),
+            file_os: u32::from_le(raw.file_os),
+            file_type: u32::from_le(raw.file_type),
+            file_subtype: u32::from_le(raw.file_subtype),
+            file_date_ms: u32::from_le(raw.file_date_ms),
+            file_date_ls: u32::from_le(raw.file_date_ls),
+        };
+        (raw.signature == SIGNATURE).then_some(raw)
+    }
+}
+
+/// Represents a binary four-part version number, e.g. `10.0.19041.1`.
+///
+/// Version numbers are ordered by their parts from the most significant
+/// `major` to the least significant `revision`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[expect(
+    clippy::arbitrary_source_item_ordering,
+    reason = "the derived `Ord` compares the parts from the most significant one",
+)]
+pub struct FileVersion {
+    /// The major version number.
+    pub major: u16,
+    /// The minor version number.
+    pub minor: u16,
+    /// The build number.
+    pub build: u16,
+    /// The revision number.
+    pub revision: u16,
+}
+
+impl FileVersion {
+    /// Creates a version number from the most and least significant halves in
+    /// which `VS_FIXEDFILEINFO` stores it, e.g. `dwFileVersionMS` and
+    /// `dwFileVersionLS`.
+    ///
+    /// Each part is decoded as an unsigned 16-bit integer, even if the tool
+    /// that wrote it meant it to wrap around, so a part with all bits set is
+    /// `65535` rather than `-1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::FileVersion;
+    ///
+    /// let version = FileVersion::from_raw(0x0001_0002, 0xFFFF_FFFF);
+    /// assert_eq!(version, FileVersion::new(1, 2, 65535, 65535));
+    /// assert_eq!(version.to_string(), "1.2.65535.65535");
+    /// ```
+    #[must_use]
+    pub const fn from_raw(ms: u32, ls: u32) -> Self {
+        Self {
+            major: (ms >> 16) as u16,
+            minor: (ms & 0xFFFF) as u16,
+            build: (ls >> 16) as u16,
+            revision: (ls & 0xFFFF) as u16,
+        }
+    }
+
+    /// Checks whether the version number is one of the
+    /// [`PLACEHOLDER_VERSIONS`], e.g. to fail a CI build shipping an artifact
+    /// whose version was never set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::{FileVersion, PLACEHOLDER_VERSIONS};
+    ///
+    /// assert!(FileVersion::new(1, 0, 0, 0).is_placeholder());
+    /// assert!(!FileVersion::new(1, 0, 0, 1).is_placeholder());
+    ///
+    /// let placeholders = [PLACEHOLDER_VERSIONS, &[FileVersion::new(1, 0, 0, 1)]].concat();
+    /// assert!(placeholders.contains(&FileVersion::new(1, 0, 0, 1)));
+    /// ```
+    #[must_use]
+    pub fn is_placeholder(self) -> bool {
+        PLACEHOLDER_VERSIONS.contains(&self)
+    }
+
+    /// Creates a version number from its four parts.
+    #[must_use]
+    pub const fn new(major: u16, minor: u16, build: u16, revision: u16) -> Self {
+        Self { major, minor, build, revision }
+    }
+
+    /// Parses one to four parts, padding the omitted ones with zeros.
+    fn parse_parts<'value>(mut values: impl Iterator<Item = &'value str>) -> Option<Self> {
+        let mut parts = [0u16; 4];
+        for (part, value) in parts.iter_mut().zip(values.by_ref()) {
+            if !value.bytes().all(|byte| byte.is_ascii_digit()) {
+                return None;
+            }
+            *part = value.parse().ok()?;
+        }
+        if values.next().is_some() {
+            return None;
+        }
+        let [major, minor, build, revision] = parts;
+        Some(Self::new(major, minor, build, revision))
+    }
+
+    /// Parses the version number at the start of a `FileVersion` or
+    /// `ProductVersion` string value, e.g. `10.0.19041.1` of
+    /// `10.0.19041.1 (WinBuild.160101.0800)`, whose parts may also be
+    /// separated by commas as in `1, 2, 3, 4`.
+    pub(crate) fn parse_string(value: &str) -> Option<Self> {
+        let version = value
+            .split(|char: char| !(char.is_ascii_digit() || matches!(char, '.' | ',' | ' ')))
+            .next()
+            .unwrap_or_default();
+        Self::parse_parts(version.trim().split(['.', ',']).map(str::trim))
+    }
+
+    /// Checks whether the version number satisfies a simple requirement, e.g.
+    /// `">= 2.1"`.
+    ///
+    /// The requirement is a comparator, one of `>=`, `>`, `=`, `<` and `<=`,
+    /// followed by a version of one to four dot-separated parts, optionally
+    /// separated by whitespace. Without a comparator, `=` is assumed. Omitted
+    /// parts are zero, so `2.1` means `2.1.0.0`.
+    ///
+    /// Versions are compared part by part from `major` to `revision`, each
+    /// part as a number, so `2.10` is greater than `2.9`. Unlike semantic
+    /// versioning, there are no pre-release or build metadata suffixes, and
+    /// `=` requires all four parts to be equal, so `= 2.1` is not satisfied
+    /// by `2.1.0.5`.
+    ///
+    /// Returns `false` if the requirement is not well-formed, e.g. a part is
+    /// not a number in `0..=65535`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::FileVersion;
+    ///
+    /// let version = FileVersion::new(2, 10, 0, 5);
+    /// assert!(version.satisfies(">= 2.1"));
+    /// assert!(version.satisfies("<3"));
+    /// assert!(!version.satisfies("= 2.10"));
+    /// assert!(version.satisfies("2.10.0.5"));
+    /// assert!(!version.satisfies(">= two"));
+    /// ```
+    #[must_use]
+    pub fn satisfies(self, req: &str) -> bool {
+        let req = req.trim();
+        let (comparator, version) = [">=", "<=", ">", "<", "="]
+            .into_iter()
+            .find_map(|comparator| Some((comparator, req.strip_prefix(comparator)?)))
+            .unwrap_or(("=", req));
+        let Some(version) = Self::parse_parts(version.trim_start().split('.')) else {
+            return false;
+        };
+        let ordering = self.cmp(&version);
+        match comparator {
+            ">=" => ordering.is_ge(),
+            "<=" => ordering.is_le(),
+            ">" => ordering.is_gt(),
+            "<" => ordering.is_lt(),
+            _ => ordering.is_eq(),
+        }
+    }
+
+    /// Formats the version number compactly, e.g. for a status bar, as
+    /// `major.minor`, followed by `.build` if the build number is not zero.
+    ///
+    /// The revision is always left out, even if it is not zero, and the major
+    /// and minor versions are always included, even if they are zero. Use the
+    /// [`Display`](fmt::Display) implementation for all four parts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::FileVersion;
+    ///
+    /// assert_eq!(FileVersion::new(1, 2, 0, 0).short_display(), "1.2");
+    /// assert_eq!(FileVersion::new(1, 2, 3, 0).short_display(), "1.2.3");
+    /// assert_eq!(FileVersion::new(1, 2, 3, 4).short_display(), "1.2.3");
+    /// assert_eq!(FileVersion::new(1, 0, 0, 4).short_display(), "1.0");
+    /// ```
+    #[must_use]
+    pub fn short_display(self) -> String {
+        if self.build == 0 {
+            format!("{}.{}", self.major, self.minor)
+        } else {
+            format!("{}.{}.{}", self.major, self.minor, self.build)
+        }
+    }
+
+    /// Returns the parts of the version number as `[major, minor, build,
+    /// revision]`, e.g. to iterate over them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::FileVersion;
+    ///
+    /// assert_eq!(FileVersion::new(10, 0, 19041, 1).to_array(), [10, 0, 19041, 1]);
+    /// ```
+    #[must_use]
+    pub const fn to_array(self) -> [u16; 4] {
+        [self.major, self.minor, self.build, self.revision]
+    }
+}
+
+impl fmt::Display for FileVersion {
+    /// Formats the version number as `major.minor.build.revision`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}.{}", self.major, self.minor, self.build, self.revision)
+    }
+}
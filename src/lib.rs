@@ -2,7 +2,8 @@
 //!
 //! ## Usage
 //!
-//! ```rust
+//! ```rust,no_run
+//! # #[cfg(windows)] {
 //! use win32_version_info::VersionInfo;
 //!
 //! let info = VersionInfo::from_file("path/to/your/file.exe")
@@ -10,23 +11,111 @@
 //!
 //! println!("File description: {}", info.file_description);
 //! println!("File version: {}", info.file_version);
+//! # }
+//! ```
+//!
+//! `VersionInfoBuilder` configures how version information is read, e.g. which
+//! translation to prefer and how string values are cleaned up:
+//!
+//! ```rust,no_run
+//! # #[cfg(windows)] {
+//! use win32_version_info::{TrailingNul, VersionInfoBuilder};
+//!
+//! let info = VersionInfoBuilder::new()
+//!     .language(Some(0x0407))
+//!     .trailing_nul(TrailingNul::StripOne)
+//!     .trim_whitespace(true)
+//!     .read("path/to/your/file.exe")
+//!     .expect("Failed to retrieve version information");
+//!
+//! println!("File description: {}", info.file_description);
+//! # }
 //! ```
 //! 
+//! ## Features
+//!
+//! - `cache`: Enables `CachedReader`, which caches version information in
+//!   memory keyed by the canonical path, the modification time and the size
+//!   of files, to skip reading unchanged files again.
+//! - `glob`: Enables reading version information from all files matching a
+//!   glob pattern with `VersionInfo::from_glob`, using the
+//!   [`glob`](https://crates.io/crates/glob) crate.
+//! - `log`: Emits debug logs with the [`log`](https://crates.io/crates/log)
+//!   crate while reading version information, e.g. the translations tried and
+//!   the string fields left empty, to diagnose unexpected results.
+//! - `manifest`: Enables reading the version of the application manifest
+//!   embedded in PE files with `manifest_version`, which is separate from
+//!   their version information. Implies `pe-parser`.
+//! - `mock`: Enables `VersionInfoOs::from_fields`, which creates version
+//!   information from string fields keyed by their names, to test code taking
+//!   version information without reading real files.
+//! - `pe-parser`: Enables reading version information from PE images in
+//!   memory with `VersionInfo::from_pe_image`, by parsing the image directly
+//!   rather than going through the loader, also for files with
+//!   `VersionInfoBuilder::bypass_loader`, and reading the architecture of PE
+//!   files with `pe_machine`.
+//! - `serde`: Implements `Serialize` and `Deserialize` from
+//!   [`serde`](https://crates.io/crates/serde) for the version information
+//!   types.
+//! - `windows` (enabled by default): Calls Win32 APIs through the
+//!   [`windows`](https://crates.io/crates/windows) crate.
+//! - `windows-sys`: Calls Win32 APIs through the raw bindings of the
+//!   [`windows-sys`](https://crates.io/crates/windows-sys) crate instead, which
+//!   is much lighter to build. The public API is the same with either backend.
+//!   Disable the default features to avoid building the `windows` crate as
+//!   well, e.g. `default-features = false, features = ["windows-sys"]`.
+//!
 //! ## Considerations
 //! 
 //! This crate is built upon the *official* Rust bindings of Win32 APIs provided
-//! by the [`windows`](https://crates.io/crates/windows) crate [maintained by
+//! by the [`windows`](https://crates.io/crates/windows) and
+//! [`windows-sys`](https://crates.io/crates/windows-sys) crates [maintained by
 //! Microsoft](https://github.com/microsoft/windows-rs).
 //! 
 //! This crate is highly inspired by the implementation of the
 //! `System.Diagnostics.FileVersionInfo` class in the
 //! [Microsoft .NET Reference Source](https://github.com/microsoft/referencesource/blob/master/System/services/monitoring/system/diagnosticts/FileVersionInfo.cs).
-//! Here I 
 //! 
 //! This crate should work in most cases but may have trouble with some rare
 //! edge cases. If you encounter any issues, please report it on the GitHub
 //! repository and I'm glad to help.
+//!
+//! Windows Installer packages (`.msi`, `.msp`) have no version resource, as they
+//! store their version in their installer database rather than in a PE
+//! resource, so reading them fails with `ErrorKind::UnsupportedFormat`. Use the
+//! Windows Installer API to read their `ProductVersion` property instead.
+//!
+//! Version information blocks are always read with the `Ex` variants of the
+//! Win32 functions, e.g. `GetFileVersionInfoExW`, which resolve the strings of
+//! MUI files that `GetFileVersionInfoW` doesn't on some versions of Windows, e.g.
+//! Windows 7. They are available on every version of Windows supported by Rust:
+//!
+//! | Windows version | Functions used                                       |
+//! | --------------- | ---------------------------------------------------- |
+//! | Vista and later | `GetFileVersionInfoSizeExW`, `GetFileVersionInfoExW` |
+//! | XP and earlier  | Unsupported, as by Rust itself                       |
+//!
+//! The localized strings read by default may still differ between versions of
+//! Windows for the same file, as they come from the MUI files installed; use
+//! `VersionInfoBuilder::neutral` to read the strings of the file itself instead.
+//!
+//! Code running in a DLL loaded by a host that may not be written in Rust should
+//! read version information with `VersionInfo::from_current_module` for the DLL
+//! and `VersionInfo::from_executable_module` for the host, which read the
+//! version resource of the loaded module rather than resolving and opening a
+//! file, whose relative paths would depend on the current directory of the host.
 //! 
+//! ## Fuzzing
+//!
+//! The parsers of version information blocks and PE images have
+//! [`cargo fuzz`](https://github.com/rust-fuzz/cargo-fuzz) targets in the `fuzz`
+//! directory, which must be run on Windows with a nightly toolchain:
+//!
+//! ```sh
+//! cargo +nightly fuzz run from_bytes
+//! cargo +nightly fuzz run from_pe_image
+//! ```
+//!
 //! ## License
 //! 
 //! Licensed under either of
@@ -49,30 +138,125 @@
 
 #![cfg(windows)]
 
+mod block;
+#[cfg(feature = "cache")]
+mod cache;
+mod fixed;
+#[cfg(feature = "manifest")]
+mod manifest;
+mod metadata;
+#[cfg(feature = "pe-parser")]
+mod pe;
+mod sys;
+mod translation;
+
+pub use fixed::{
+    FileOs,
+    FileOsBase,
+    FileOsSubsystem,
+    FileVersion,
+    FixedFileInfo,
+    VsFixedFileInfo,
+    PLACEHOLDER_VERSIONS,
+};
+#[cfg(feature = "cache")]
+pub use cache::CachedReader;
+#[cfg(feature = "manifest")]
+pub use manifest::manifest_version;
+pub use metadata::FileMetadata;
+#[cfg(feature = "pe-parser")]
+pub use pe::Machine;
+pub use translation::{
+    ParseTranslationError,
+    Translation,
+};
+
 use std::{
+    array,
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
+    error,
     ffi,
-    ffi::OsString,
+    ffi::{
+        OsStr,
+        OsString,
+    },
+    fmt,
+    fs,
+    fs::File,
+    io,
+    io::{
+        Read as _,
+        Seek as _,
+        SeekFrom,
+    },
+    iter,
+    mem,
     os::windows::prelude::{
+        AsHandle,
+        BorrowedHandle,
         OsStrExt,
         OsStringExt,
     },
-    path::Path,
+    panic,
+    path::{
+        Component,
+        Path,
+        PathBuf,
+    },
     ptr,
+    result,
+    hash::{
+        Hash,
+        Hasher,
+    },
     slice,
+    sync::{
+        atomic::{
+            AtomicBool,
+            AtomicUsize,
+            Ordering,
+        },
+        Arc,
+    },
+    thread,
+    time::Duration,
 };
 
-use windows::core::{
-    Error,
-    Result,
-    PCWSTR,
-    w as pcwstr,
-};
+/// Emits a debug log with the `log` crate if the `log` feature is enabled.
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "log")]
+        log::debug!($($arg)+);
+    };
+}
 
-use windows::Win32::Storage::FileSystem::{
-    GetFileVersionInfoSizeW,
-    GetFileVersionInfoW,
-    VerQueryValueW,
-};
+/// The names of the standard string fields in the version information, in the
+/// order of the fields of [`VersionInfo`] and [`VersionInfoOs`].
+///
+/// These are the keys as stored in the `StringFileInfo` block of the file,
+/// e.g. `"FileDescription"` for [`VersionInfo::file_description`].
+pub const STANDARD_FIELDS: &[&str] = &[
+    "Comments",
+    "CompanyName",
+    "FileDescription",
+    "FileVersion",
+    "InternalName",
+    "LegalCopyright",
+    "LegalTrademarks",
+    "OriginalFilename",
+    "ProductName",
+    "ProductVersion",
+    "PrivateBuild",
+    "SpecialBuild",
+];
+
+/// The maximum length of a path in UTF-16 code units, excluding the
+/// terminating NUL. The length of a `UNICODE_STRING` in bytes is a `u16`, so no
+/// path, not even a verbatim one, can be longer than this.
+const MAX_PATH_LEN: usize = 0x7FFF;
 
 /// Represents version information for a file.
 ///
@@ -87,6 +271,7 @@ use windows::Win32::Storage::FileSystem::{
 /// data, use [`VersionInfoOs`] instead.
 /// 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub struct VersionInfo {
     /// The comments associated with the file.
@@ -97,6 +282,12 @@ pub struct VersionInfo {
     pub file_description: String,
     /// The file version number.
     pub file_version: String,
+    /// The fixed, language-independent part of the version information, if
+    /// the file has one.
+    ///
+    /// It is read from the root block `\`, outside of any string table, so it
+    /// is the same whichever translation the string fields are read from.
+    pub fixed_file_info: Option<FixedFileInfo>,
     /// The internal name of the file, if one exists.
     pub internal_name: String,
     /// The copyright notices that apply to the specified file.
@@ -116,6 +307,283 @@ pub struct VersionInfo {
 }
 
 impl VersionInfo {
+    /// Returns the numeric file version, preferring the one of
+    /// [`VersionInfo::fixed_file_info`] and falling back to parsing the
+    /// [`VersionInfo::file_version`] string.
+    ///
+    /// A fixed file version of `0.0.0.0` is treated as missing, as tools
+    /// filling only the string leave it so. The string is parsed as in
+    /// [`VersionInfo::version_strings_consistent`], e.g. `1.2` is `1.2.0.0`.
+    ///
+    /// Returns `None` if neither is present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::{FileVersion, VersionInfo};
+    ///
+    /// let mut info = VersionInfo::default();
+    /// info.file_version = "1, 2, 3, 4".to_owned();
+    /// assert_eq!(info.best_file_version(), Some(FileVersion::new(1, 2, 3, 4)));
+    /// ```
+    #[must_use]
+    pub fn best_file_version(&self) -> Option<FileVersion> {
+        Self::best_version(
+            self.fixed_file_info.map(|fixed_file_info| fixed_file_info.file_version),
+            &self.file_version)
+    }
+
+    /// Returns the numeric product version, preferring the one of
+    /// [`VersionInfo::fixed_file_info`] and falling back to parsing the
+    /// [`VersionInfo::product_version`] string, e.g. for installers filling
+    /// only one of them.
+    ///
+    /// See [`VersionInfo::best_file_version`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::{FileVersion, VersionInfo};
+    ///
+    /// let mut info = VersionInfo::default();
+    /// info.product_version = "2.5 (beta)".to_owned();
+    /// assert_eq!(info.best_product_version(), Some(FileVersion::new(2, 5, 0, 0)));
+    /// ```
+    #[must_use]
+    pub fn best_product_version(&self) -> Option<FileVersion> {
+        Self::best_version(
+            self.fixed_file_info.map(|fixed_file_info| fixed_file_info.product_version),
+            &self.product_version)
+    }
+
+    fn best_version(fixed: Option<FileVersion>, string: &str) -> Option<FileVersion> {
+        fixed
+            .filter(|version| *version != FileVersion::default())
+            .or_else(|| FileVersion::parse_string(string))
+    }
+
+    /// Joins [`VersionInfo::company_name`] and [`VersionInfo::product_name`]
+    /// with an em dash, e.g. for logging, omitting either of them if empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::VersionInfo;
+    ///
+    /// let mut info = VersionInfo::default();
+    /// info.company_name = "Microsoft Corporation".to_owned();
+    /// info.product_name = "Microsoft® Windows® Operating System".to_owned();
+    /// assert_eq!(
+    ///     info.company_and_product(),
+    ///     "Microsoft Corporation — Microsoft® Windows® Operating System");
+    ///
+    /// info.company_name.clear();
+    /// assert_eq!(info.company_and_product(), "Microsoft® Windows® Operating System");
+    /// ```
+    #[must_use]
+    pub fn company_and_product(&self) -> String {
+        [self.company_name.as_str(), self.product_name.as_str()]
+            .into_iter()
+            .filter(|name| !name.is_empty())
+            .collect::<Vec<_>>()
+            .join(" \u{2014} ")
+    }
+
+    /// Extracts the years from [`VersionInfo::legal_copyright`], e.g.
+    /// `(2004, Some(2023))` from `Copyright © 2004-2023 Contoso`, to estimate
+    /// the age of the software.
+    ///
+    /// Years are standalone four-digit numbers from 1900 to 2099. The
+    /// earliest and the latest of them are returned, so lists of years such
+    /// as `1998, 2003-2005` are handled as the range `1998` to `2005`, and a
+    /// single year is returned as `(year, None)`.
+    ///
+    /// Returns `None` if the copyright mentions no year.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::VersionInfo;
+    ///
+    /// let mut info = VersionInfo::default();
+    /// info.legal_copyright = "Copyright © 2004-2023 Contoso".to_owned();
+    /// assert_eq!(info.copyright_years(), Some((2004, Some(2023))));
+    ///
+    /// info.legal_copyright = "(C) 2019 Contoso".to_owned();
+    /// assert_eq!(info.copyright_years(), Some((2019, None)));
+    ///
+    /// info.legal_copyright = "All rights reserved.".to_owned();
+    /// assert_eq!(info.copyright_years(), None);
+    /// ```
+    #[must_use]
+    pub fn copyright_years(&self) -> Option<(u16, Option<u16>)> {
+        let (first, last) = self.legal_copyright
+            .split(|char: char| !char.is_ascii_digit())
+            .filter(|digits| digits.len() == 4)
+            .filter_map(|digits| digits.parse::<u16>().ok())
+            .filter(|year| (1900..=2099).contains(year))
+            .fold(None, |years, year| match years {
+                Some((first, last)) => Some((u16::min(first, year), u16::max(last, year))),
+                None => Some((year, year)),
+            })?;
+        Some((first, (last != first).then_some(last)))
+    }
+
+    /// Checks whether two files have the same version information, comparing
+    /// the string fields case-insensitively, e.g. so that `ACME` and `Acme`
+    /// are the same [`VersionInfo::company_name`].
+    ///
+    /// The comparison is Unicode-aware: the fields are compared after mapping
+    /// every character to lowercase with [`char::to_lowercase`], so e.g. `Ä`
+    /// and `ä` are equal. This is not full case folding, under which `ß` and
+    /// `SS` would also be equal. [`VersionInfo::fixed_file_info`] is compared
+    /// exactly, as with `==`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::VersionInfo;
+    ///
+    /// let mut upper = VersionInfo::default();
+    /// upper.company_name = "ACME".to_owned();
+    /// let mut title = VersionInfo::default();
+    /// title.company_name = "Acme".to_owned();
+    /// assert!(upper.eq_ignore_case(&title));
+    /// assert_ne!(upper, title);
+    /// ```
+    #[must_use]
+    pub fn eq_ignore_case(&self, other: &Self) -> bool {
+        self.fixed_file_info == other.fixed_file_info
+            && self.fields().zip(other.fields()).all(|((_, value), (_, other_value))| {
+                value.chars()
+                    .flat_map(char::to_lowercase)
+                    .eq(other_value.chars().flat_map(char::to_lowercase))
+            })
+    }
+
+    /// Iterates over the string fields as `(name, value)` pairs, in the order
+    /// of [`STANDARD_FIELDS`], e.g. `("FileDescription", "Notepad")`.
+    ///
+    /// `&VersionInfo` also implements [`IntoIterator`] with the same items.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::collections::HashMap;
+    /// use win32_version_info::VersionInfo;
+    ///
+    /// let info = VersionInfo::from_file("path/to/your/file.exe")
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// let fields: HashMap<_, _> = info.fields().collect();
+    /// println!("File description: {}", fields["FileDescription"]);
+    /// ```
+    #[must_use]
+    pub fn fields(&self) -> Fields<'_> {
+        Fields(STANDARD_FIELDS.iter().copied().zip([
+            self.comments.as_str(),
+            self.company_name.as_str(),
+            self.file_description.as_str(),
+            self.file_version.as_str(),
+            self.internal_name.as_str(),
+            self.legal_copyright.as_str(),
+            self.legal_trademarks.as_str(),
+            self.original_filename.as_str(),
+            self.product_name.as_str(),
+            self.product_version.as_str(),
+            self.private_build.as_str(),
+            self.special_build.as_str(),
+        ]))
+    }
+
+    /// Renders the binary file version from [`VersionInfo::fixed_file_info`]
+    /// as `major.minor.build.revision`, e.g. `10.0.19041.1`.
+    ///
+    /// Unlike [`VersionInfo::file_version`], which is free-form text written by
+    /// the vendor, this is always a plain dotted quad, making it suitable for
+    /// consistent logging and comparison. Returns `None` if the file has no
+    /// fixed version information.
+    #[must_use]
+    pub fn file_version_string_normalized(&self) -> Option<String> {
+        self.fixed_file_info
+            .map(|fixed_file_info| fixed_file_info.file_version.to_string())
+    }
+
+    /// Checks whether [`VersionInfo::original_filename`] matches the file name
+    /// of `path`, ignoring case, e.g. to detect a renamed or repackaged
+    /// binary.
+    ///
+    /// Only the last component of `path` is compared, and it is compared to
+    /// the original file name with surrounding whitespace trimmed. Letters are
+    /// compared by their lowercase forms, so `NOTEPAD.EXE` matches
+    /// `notepad.exe`.
+    ///
+    /// Returns `None` if the original file name is empty or `path` has no
+    /// file name, e.g. `C:\`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::VersionInfo;
+    ///
+    /// let mut info = VersionInfo::default();
+    /// assert_eq!(info.filename_matches_original(r"C:\Windows\notepad.exe"), None);
+    ///
+    /// info.original_filename = "NOTEPAD.EXE".to_owned();
+    /// assert_eq!(info.filename_matches_original(r"C:\Windows\notepad.exe"), Some(true));
+    /// assert_eq!(info.filename_matches_original(r"C:\Temp\invoice.exe"), Some(false));
+    /// ```
+    #[must_use]
+    pub fn filename_matches_original<P: AsRef<Path>>(&self, path: P) -> Option<bool> {
+        let original_filename = self.original_filename.trim();
+        if original_filename.is_empty() {
+            return None;
+        }
+        let file_name = path.as_ref().file_name()?.to_string_lossy();
+        Some(file_name.to_lowercase() == original_filename.to_lowercase())
+    }
+
+    /// Retrieves version information from a raw version information block.
+    ///
+    /// See [`VersionInfoOs::from_bytes`] for details.
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`ErrorKind::MalformedBlock`] if `data` is not a
+    /// well-formed version information block.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        VersionInfoOs::from_bytes(data).map(|info| Self::from_os(&info))
+    }
+
+    /// Retrieves version information from the module containing this crate,
+    /// e.g. the DLL it is linked into.
+    ///
+    /// See [`VersionInfoOs::from_current_module`] for details.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The module has no version information, see [`ErrorKind::NoVersionInfo`].
+    /// - The version resource is malformed, see [`ErrorKind::MalformedBlock`].
+    pub fn from_current_module() -> Result<Self> {
+        VersionInfoOs::from_current_module().map(|info| Self::from_os(&info))
+    }
+
+    /// Retrieves version information from the executable of the current
+    /// process, e.g. the host application of a DLL.
+    ///
+    /// See [`VersionInfoOs::from_executable_module`] for details.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The executable has no version information, see
+    ///   [`ErrorKind::NoVersionInfo`].
+    /// - The version resource is malformed, see [`ErrorKind::MalformedBlock`].
+    pub fn from_executable_module() -> Result<Self> {
+        VersionInfoOs::from_executable_module().map(|info| Self::from_os(&info))
+    }
+
     /// Retrieves version information from the specified file.
     /// 
     /// As [`VersionInfo`] uses [`String`] for its string fields, any possibly
@@ -123,6 +591,9 @@ impl VersionInfo {
     /// of the file will be replaced with the Unicode replacement character (�)
     /// when converting to a [`String`]. If you need to preserve such data, use
     /// [`VersionInfoOs::from_file`] instead.
+    ///
+    /// The path may use forward slashes as separators, e.g.
+    /// `C:/Windows/System32/kernel32.dll`, see [`VersionInfoBlock::from_file`].
     /// 
     /// # Errors
     ///
@@ -130,6 +601,12 @@ impl VersionInfo {
     /// - The file does not exist.
     /// - The file is not accessible.
     /// - The version information cannot be retrieved.
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    /// - The file is a legacy 16-bit executable, see
+    ///   [`ErrorKind::LegacyExecutableUnsupported`].
+    /// - The file is a Windows Installer package, see
+    ///   [`ErrorKind::UnsupportedFormat`].
+    /// - The path is too long, see [`ErrorKind::PathTooLong`].
     ///
     /// # Examples
     ///
@@ -143,198 +620,3301 @@ impl VersionInfo {
     /// println!("File version: {}", info.file_version);
     /// ```
     pub fn from_file<P: AsRef<Path>>(file_name: P) -> Result<Self> {
-        let info = VersionInfoOs::from_file(file_name)?;
-        Ok(Self {
-            comments: info.comments.to_string_lossy().into_owned(),
-            company_name: info.company_name.to_string_lossy().into_owned(),
-            file_description: info.file_description.to_string_lossy().into_owned(),
-            file_version: info.file_version.to_string_lossy().into_owned(),
-            internal_name: info.internal_name.to_string_lossy().into_owned(),
-            legal_copyright: info.legal_copyright.to_string_lossy().into_owned(),
-            legal_trademarks: info.legal_trademarks.to_string_lossy().into_owned(),
-            original_filename: info.original_filename.to_string_lossy().into_owned(),
-            product_name: info.product_name.to_string_lossy().into_owned(),
-            product_version: info.product_version.to_string_lossy().into_owned(),
-            private_build: info.private_build.to_string_lossy().into_owned(),
-            special_build: info.special_build.to_string_lossy().into_owned(),
-        })
+        VersionInfoOs::from_file(file_name).map(|info| Self::from_os(&info))
     }
-}
-
-/// Represents version information for a file.
-///
-/// This struct contains various fields that provide detailed information
-/// about the file, such as its description and version number, company that
-/// produced it, and other metadata.
-/// 
-/// This struct is similar to [`VersionInfo`], but it uses [`OsString`] instead
-/// of [`String`] for its string fields to preserve any possibly ill-formed
-/// UTF-16 data that may be present in the version information of the file.
-/// 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
-#[non_exhaustive]
-pub struct VersionInfoOs {
-    /// The comments associated with the file.
-    pub comments: OsString,
-    /// The name of the company that produced the file.
-    pub company_name: OsString,
-    /// The description of the file.
-    pub file_description: OsString,
-    /// The file version number.
-    pub file_version: OsString,
-    /// The internal name of the file, if one exists.
-    pub internal_name: OsString,
-    /// The copyright notices that apply to the specified file.
-    pub legal_copyright: OsString,
-    /// The trademarks and registered trademarks that apply to the file.
-    pub legal_trademarks: OsString,
-    /// The name the file was created with.
-    pub original_filename: OsString,
-    /// The name of the product this file is distributed with.
-    pub product_name: OsString,
-    /// The version of the product this file is distributed with.
-    pub product_version: OsString,
-    /// The private build information for the file.
-    pub private_build: OsString,
-    /// The special build information for the file.
-    pub special_build: OsString,
-}
 
-impl VersionInfoOs {
-    /// Retrieves version information from the specified file.
-    /// 
-    /// This function is similar to [`VersionInfo::from_file`], but it uses
-    /// [`OsString`] instead of [`String`] for its string fields to preserve any
-    /// possibly ill-formed UTF-16 data that may be present in the version
-    /// information of the file.
-    /// 
+    /// Retrieves version information from the specified file, whose path is
+    /// encoded in the ANSI code page, with `GetFileVersionInfoExA`.
+    ///
+    /// See [`VersionInfoOs::from_file_ansi`] for details.
+    ///
     /// # Errors
-    /// 
+    ///
     /// This function will return an error if:
     /// - The file does not exist.
     /// - The file is not accessible.
     /// - The version information cannot be retrieved.
-    /// 
-    /// # Examples
-    /// 
-    /// ```
-    /// use your_crate_name::VersionInfoOs;
-    /// 
-    /// let info = VersionInfoOs::from_file("path/to/your/file.exe")
-    ///    .expect("Failed to retrieve version information");
-    /// 
-    /// println!("File description: {}", info.file_description.to_string_lossy());
-    /// println!("File version: {}", info.file_version.to_string_lossy());
-    /// ```
-    /// 
-    pub fn from_file<P: AsRef<Path>>(file_name: P) -> Result<Self> {
-        const LANG_US_ENGLISH_CP_UNKNOWN: u32 = 0x04090000;
-        const LANG_US_ENGLISH_CP_UNICODE: u32 = 0x040904B0;
-        const LANG_US_ENGLISH_CP_USASCII: u32 = 0x040904E4;
-        let ver_data = VersionInfoInternal::from_file(file_name.as_ref())?;
-        let ver_info = Self::default();
-        Ok(ver_data.get_translation_id()
-            .into_iter()
-            .chain([
-                // anyway, these fallback values are exactly what .NET Framework uses =_=
-                LANG_US_ENGLISH_CP_UNICODE,
-                LANG_US_ENGLISH_CP_USASCII,
-                LANG_US_ENGLISH_CP_UNKNOWN,
-            ])
-            .map(|translation_id| {
-                let mut ver_info = ver_info.clone();
-                ver_data.get_all_fields_in_translation(translation_id, &mut ver_info);
-                ver_info
-            })
-            .find(|ver_info| !ver_info.file_version.is_empty())
-            .unwrap_or_default())
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    pub fn from_file_ansi(file_name: &ffi::CStr) -> Result<Self> {
+        VersionInfoOs::from_file_ansi(file_name).map(|info| Self::from_os(&info))
     }
-}
 
-struct VersionInfoInternal(Vec<u8>);
-impl VersionInfoInternal {
-    fn from_file<P: AsRef<Path>>(file_name: P) -> Result<Self> {
-        let file_name = file_name
-            .as_ref()
-            .as_os_str()
-            .encode_wide()
-            .chain(Some(0))
-            .collect::<Vec<_>>();
-        let size = unsafe {
-            GetFileVersionInfoSizeW(PCWSTR(file_name.as_ptr()), None)
-        };
-        if size > 0 {
-            let mut data = vec![0u8; size as usize];
-            unsafe {
-                GetFileVersionInfoW(
-                    PCWSTR(file_name.as_ptr()),
-                    0,
-                    size,
-                    data.as_mut_ptr().cast())
-            }?;
-            Ok(Self(data))
-        } else {
-            Err(Error::from_win32())
-        }
+    /// Retrieves version information from the file at a path relative to an
+    /// open directory.
+    ///
+    /// See [`VersionInfoOs::from_file_at`] for details.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - `file_name` is absolute or has a `..` component.
+    /// - The path of the directory cannot be resolved from its handle.
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    pub fn from_file_at<H: AsHandle, P: AsRef<Path>>(dir: H, file_name: P) -> Result<Self> {
+        VersionInfoOs::from_file_at(dir, file_name).map(|info| Self::from_os(&info))
     }
 
-    fn get_translation_id(&self) -> Option<u32> {
-        self.get_value_by_path(pcwstr!("\\VarFileInfo\\Translation"))
-            .filter(|&(_, len)| len >= 4)
-            .map(|(ptr, _)| unsafe {
-                ptr::read_unaligned::<u32>(ptr.cast())
-                    .rotate_right(16)
-            })
+    /// Retrieves version information from the specified file, preferring the
+    /// translation in the UI language of the current thread.
+    ///
+    /// See [`VersionInfoOs::from_file_current_locale`] for details.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    pub fn from_file_current_locale<P: AsRef<Path>>(file_name: P) -> Result<Self> {
+        VersionInfoOs::from_file_current_locale(file_name).map(|info| Self::from_os(&info))
     }
 
-    fn get_all_fields_in_translation(
-        &self,
-        translation_id: u32,
-        info: &mut VersionInfoOs) {
-        info.comments          = self.get_field_in_translation("Comments", translation_id);
-        info.company_name      = self.get_field_in_translation("CompanyName", translation_id);
-        info.file_description  = self.get_field_in_translation("FileDescription", translation_id);
-        info.file_version      = self.get_field_in_translation("FileVersion", translation_id);
-        info.internal_name     = self.get_field_in_translation("InternalName", translation_id);
-        info.legal_copyright   = self.get_field_in_translation("LegalCopyright", translation_id);
-        info.legal_trademarks  = self.get_field_in_translation("LegalTrademarks", translation_id);
-        info.original_filename = self.get_field_in_translation("OriginalFilename", translation_id);
-        info.product_name      = self.get_field_in_translation("ProductName", translation_id);
-        info.product_version   = self.get_field_in_translation("ProductVersion", translation_id);
-        info.private_build     = self.get_field_in_translation("PrivateBuild", translation_id);
-        info.special_build     = self.get_field_in_translation("SpecialBuild", translation_id);
-    }
-
-    fn get_field_in_translation(&self, name: &str, translation_id: u32) -> OsString {
-        let path =
-            format!("\\StringFileInfo\\{translation_id:08x}\\{name}")
-                .encode_utf16()
-                .chain(Some(0))
-                .collect::<Vec<_>>();
-        self.get_value_by_path(PCWSTR(path.as_ptr()))
-            .map(|(ptr, len)| OsString::from_wide({
-                let mut slice = unsafe {
-                    slice::from_raw_parts(ptr.cast(), len)
-                };
-                while slice.last() == Some(&0) {
-                    slice = &slice[..slice.len() - 1];
-                }
-                slice
-            }))
-            .unwrap_or_default()
-    }
-
-    fn get_value_by_path(&self, path: PCWSTR)
-    -> Option<(*const ffi::c_void, usize)> {
-        let mut ptr = ptr::null_mut();
-        let mut len = 0;
-        unsafe {
-            VerQueryValueW(
-                self.0.as_ptr().cast(),
-                PCWSTR(path.as_ptr()),
-                &mut ptr,
-                &mut len)
-        }   .as_bool()
-            .then(|| (ptr.cast_const(), len as usize))
+    /// Retrieves both the language-neutral and the localized version
+    /// information of the specified file, as `(neutral, localized)`.
+    ///
+    /// See [`VersionInfoOs::from_file_dual`] for details.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    pub fn from_file_dual<P: AsRef<Path>>(file_name: P) -> Result<(Self, Self)> {
+        VersionInfoBuilder::new().read_dual(file_name)
     }
+
+    /// Retrieves version information from the specified file, along with the
+    /// size in bytes of its version information block, e.g. to track the
+    /// size of version resources in build-size analysis.
+    ///
+    /// The size is the one reported by `GetFileVersionInfoSizeExW`, i.e. the
+    /// length of [`VersionInfoBlock::as_bytes`]. It is somewhat larger than
+    /// the `RT_VERSION` resource itself, as Windows reserves room after the
+    /// block, but grows with it.
+    ///
+    /// The version information is read exactly as in
+    /// [`VersionInfo::from_file`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::VersionInfo;
+    ///
+    /// let (info, size) = VersionInfo::from_file_with_size("path/to/your/file.exe")
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// println!("{}: {size} bytes of version information", info.product_name);
+    /// ```
+    pub fn from_file_with_size<P: AsRef<Path>>(file_name: P) -> Result<(Self, usize)> {
+        let builder = VersionInfoBuilder::new();
+        let ver_data = builder.read_block(file_name.as_ref())?;
+        let ver_info = builder.read_internal(&ver_data)?;
+        Ok((Self::from_os(&ver_info), ver_data.as_bytes().len()))
+    }
+
+    /// Retrieves the string fields of exactly the specified translation of
+    /// the specified file, e.g. to read its Unicode string table rather than
+    /// an ANSI one in the same language.
+    ///
+    /// See [`VersionInfoOs::from_file_with_translation`] for details.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    /// - The file has no string table for `translation`, see
+    ///   [`ErrorKind::TranslationNotFound`].
+    pub fn from_file_with_translation<P: AsRef<Path>>(
+        file_name: P,
+        translation: Translation) -> Result<Self> {
+        VersionInfoBuilder::new()
+            .translation_exact(Some(translation))
+            .disable_fallbacks(true)
+            .read(file_name)
+    }
+
+    /// Retrieves version information from each of the specified files,
+    /// returning the results keyed by the paths as given.
+    ///
+    /// See [`VersionInfoBuilder::read_batch`] for details.
+    pub fn from_files<I, P>(file_names: I) -> HashMap<PathBuf, Result<Self>>
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf> {
+        VersionInfoBuilder::new().read_batch(file_names)
+    }
+
+    /// Retrieves version information from every file matching a glob pattern,
+    /// e.g. `C:\Windows\System32\*.dll`.
+    ///
+    /// See [`VersionInfoBuilder::read_glob`] for details.
+    ///
+    /// This function requires the `glob` feature.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `pattern` is not a valid glob
+    /// pattern. Errors reading the matched files are yielded by the returned
+    /// iterator instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::VersionInfo;
+    ///
+    /// let infos = VersionInfo::from_glob(r"C:\Windows\System32\*.dll")
+    ///     .expect("Invalid glob pattern");
+    ///
+    /// for (path, info) in infos {
+    ///     if let Ok(Some(info)) = info {
+    ///         println!("{}: {}", path.display(), info.file_version);
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "glob")]
+    pub fn from_glob(pattern: &str) -> result::Result<
+        impl Iterator<Item = (PathBuf, Result<Option<Self>>)>,
+        glob::PatternError> {
+        VersionInfoBuilder::new().read_glob(pattern)
+    }
+
+    fn from_os(info: &VersionInfoOs) -> Self {
+        Self::from_os_with(info, InvalidUtf16Policy::default())
+    }
+
+    fn from_os_with(info: &VersionInfoOs, policy: InvalidUtf16Policy) -> Self {
+        Self {
+            comments: policy.decode(&info.comments),
+            company_name: policy.decode(&info.company_name),
+            file_description: policy.decode(&info.file_description),
+            file_version: policy.decode(&info.file_version),
+            internal_name: policy.decode(&info.internal_name),
+            legal_copyright: policy.decode(&info.legal_copyright),
+            legal_trademarks: policy.decode(&info.legal_trademarks),
+            original_filename: policy.decode(&info.original_filename),
+            product_name: policy.decode(&info.product_name),
+            product_version: policy.decode(&info.product_version),
+            private_build: policy.decode(&info.private_build),
+            special_build: policy.decode(&info.special_build),
+            fixed_file_info: info.fixed_file_info,
+        }
+    }
+
+    /// Retrieves version information from a PE image in memory.
+    ///
+    /// See [`VersionInfoOs::from_pe_image`] for details.
+    ///
+    /// This function requires the `pe-parser` feature.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - `image` is not a valid PE image, see [`ErrorKind::InvalidPeImage`].
+    /// - The image has no version resource, see [`ErrorKind::NoVersionInfo`].
+    /// - The version resource is malformed, see [`ErrorKind::MalformedBlock`].
+    #[cfg(feature = "pe-parser")]
+    pub fn from_pe_image(image: &[u8]) -> Result<Self> {
+        VersionInfoOs::from_pe_image(image).map(|info| Self::from_os(&info))
+    }
+
+    /// Retrieves version information from a PE image starting at `offset`
+    /// within `data`.
+    ///
+    /// See [`VersionInfoOs::from_pe_image_at`] for details.
+    ///
+    /// This function requires the `pe-parser` feature.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - There is no valid PE image at `offset`, see
+    ///   [`ErrorKind::InvalidPeImage`].
+    /// - The image has no version resource, see [`ErrorKind::NoVersionInfo`].
+    /// - The version resource is malformed, see [`ErrorKind::MalformedBlock`].
+    #[cfg(feature = "pe-parser")]
+    pub fn from_pe_image_at(data: &[u8], offset: usize) -> Result<Self> {
+        VersionInfoOs::from_pe_image_at(data, offset).map(|info| Self::from_os(&info))
+    }
+
+    /// Retrieves version information from a PE image read from `reader`,
+    /// starting at its current position.
+    ///
+    /// See [`VersionInfoOs::from_pe_reader`] for details.
+    ///
+    /// This function requires the `pe-parser` feature.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - Reading or seeking fails.
+    /// - The image is not a valid PE image, see [`ErrorKind::InvalidPeImage`].
+    /// - The image has no version resource, see [`ErrorKind::NoVersionInfo`].
+    /// - The version resource is malformed, see [`ErrorKind::MalformedBlock`].
+    #[cfg(feature = "pe-parser")]
+    pub fn from_pe_reader<R: io::Read + io::Seek>(reader: R) -> Result<Self> {
+        VersionInfoOs::from_pe_reader(reader).map(|info| Self::from_os(&info))
+    }
+
+    /// Retrieves version information from the main executable image of the
+    /// process with the specified process ID.
+    ///
+    /// See [`VersionInfoOs::from_process_id`] for details.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The process does not exist.
+    /// - The process cannot be opened, e.g. it is a protected process. In this
+    ///   case the error is [`ErrorKind::ProcessAccessDenied`] with the process
+    ///   ID.
+    /// - The version information cannot be retrieved.
+    pub fn from_process_id(pid: u32) -> Result<Self> {
+        VersionInfoOs::from_process_id(pid).map(|info| Self::from_os(&info))
+    }
+
+    /// Hashes the fields identifying a build, e.g. to group identical copies
+    /// of the same file across many machines.
+    ///
+    /// The hashed fields are [`VersionInfo::company_name`],
+    /// [`VersionInfo::product_name`], the file version and the product
+    /// version. The versions are the numeric ones of
+    /// [`VersionInfo::fixed_file_info`] if present and not `0.0.0.0`, and the
+    /// strings [`VersionInfo::file_version`] and
+    /// [`VersionInfo::product_version`] otherwise. All other fields, e.g.
+    /// [`VersionInfo::comments`], are ignored.
+    ///
+    /// The hash is 64-bit FNV-1a, so unlike [`Hash`] with the hashers of the
+    /// standard library, it is the same on every machine and does not change
+    /// across Rust or crate versions, which makes it suitable for storing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::VersionInfo;
+    ///
+    /// let mut info = VersionInfo::default();
+    /// info.company_name = "Contoso".to_owned();
+    /// info.product_name = "Widget".to_owned();
+    /// info.file_version = "1.2".to_owned();
+    /// let mut commented = info.clone();
+    /// commented.comments = "Built on a Friday".to_owned();
+    /// assert_eq!(info.hash_identity(), commented.hash_identity());
+    /// ```
+    #[must_use]
+    pub fn hash_identity(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const FNV_PRIME: u64 = 0x100000001b3;
+        self.identity()
+            .into_iter()
+            .fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME))
+    }
+
+    /// The bytes of the fields identifying a build, see
+    /// [`VersionInfo::hash_identity`].
+    fn identity(&self) -> Vec<u8> {
+        // 0xFF never occurs in UTF-8, so it terminates the string fields
+        // unambiguously. It also marks a numeric version, whose little-endian
+        // bytes may contain 0xFF as well, but which always has 8 bytes, so
+        // the fields stay unambiguous.
+        const SEPARATOR: u8 = 0xFF;
+        // an all-zero numeric version is a placeholder left by many
+        // toolchains, so the string identifies the build then
+        let version = |fixed: Option<FileVersion>, string: &str| fixed
+            .filter(|fixed| *fixed != FileVersion::default())
+            .map_or_else(
+                || string.bytes().collect(),
+                |fixed| iter::once(SEPARATOR)
+                    .chain(fixed.to_array().into_iter().flat_map(u16::to_le_bytes))
+                    .collect::<Vec<_>>());
+        let file_version = version(
+            self.fixed_file_info.map(|fixed_file_info| fixed_file_info.file_version),
+            &self.file_version);
+        let product_version = version(
+            self.fixed_file_info.map(|fixed_file_info| fixed_file_info.product_version),
+            &self.product_version);
+        [
+            self.company_name.as_bytes(),
+            self.product_name.as_bytes(),
+            &file_version,
+            &product_version,
+        ]
+            .into_iter()
+            .flat_map(|field| field.iter().copied().chain(iter::once(SEPARATOR)))
+            .collect()
+    }
+
+    /// Returns `true` if all string fields are empty.
+    ///
+    /// Reading a file without a version resource fails with
+    /// [`ErrorKind::NoVersionInfo`], while reading a file whose version
+    /// resource has no usable string table, e.g. one with only
+    /// [`VersionInfo::fixed_file_info`] or one whose string tables are all
+    /// in undeclared translations, succeeds with all string fields empty.
+    /// This tells the latter apart from a file with string fields.
+    ///
+    /// [`VersionInfo::fixed_file_info`] is not taken into account.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::{ErrorKind, VersionInfo};
+    ///
+    /// match VersionInfo::from_file("path/to/your/file.exe") {
+    ///     Ok(info) if info.is_empty() => println!("No string fields"),
+    ///     Ok(info) => println!("File description: {}", info.file_description),
+    ///     Err(err) if matches!(err.kind(), ErrorKind::NoVersionInfo) => {
+    ///         println!("No version information");
+    ///     }
+    ///     Err(err) => eprintln!("{err}"),
+    /// }
+    /// ```
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.fields().all(|(_, value)| value.is_empty())
+    }
+
+    /// Guesses from [`VersionInfo::company_name`] whether the file comes from
+    /// Microsoft, e.g. as a first-pass filter of an inventory.
+    ///
+    /// **This is NOT a signature check.** Any file can claim any company name,
+    /// so this must never be used for security decisions, only to pick the
+    /// files worth verifying, e.g. with `WinVerifyTrust`, which checks the
+    /// Authenticode signature.
+    ///
+    /// The company name is compared ignoring case, surrounding and repeated
+    /// whitespace and a trailing period to the names Microsoft uses, i.e.
+    /// `Microsoft Corporation`, `Microsoft Corp` and `Microsoft`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::VersionInfo;
+    ///
+    /// let mut info = VersionInfo::default();
+    /// info.company_name = " Microsoft  Corp. ".to_owned();
+    /// assert!(info.is_microsoft_signed_hint());
+    ///
+    /// info.company_name = "Microsoft Corporation Impersonators Ltd".to_owned();
+    /// assert!(!info.is_microsoft_signed_hint());
+    /// ```
+    #[must_use]
+    pub fn is_microsoft_signed_hint(&self) -> bool {
+        const MICROSOFT_COMPANY_NAMES: [&str; 3] = ["Microsoft Corporation", "Microsoft Corp", "Microsoft"];
+        let company_name = self.company_name.split_whitespace().collect::<Vec<_>>().join(" ");
+        let company_name = company_name.strip_suffix('.').unwrap_or(&company_name);
+        MICROSOFT_COMPANY_NAMES
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(company_name))
+    }
+
+    /// Checks whether the binary file version from
+    /// [`VersionInfo::fixed_file_info`] is newer than that of the specified
+    /// file, e.g. to check whether an update is installed.
+    ///
+    /// Only the fixed version information of the other file is read. If
+    /// either file has no fixed version information, `absent` decides
+    /// whether to fail or to treat the missing version as older than any
+    /// other, see [`AbsentVersion`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The other file does not exist.
+    /// - The other file is not accessible.
+    /// - The version information of the other file cannot be retrieved.
+    /// - Either file has no fixed version information and `absent` is
+    ///   [`AbsentVersion::Error`], see [`ErrorKind::NoVersionInfo`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::{AbsentVersion, VersionInfo};
+    ///
+    /// let update = VersionInfo::from_file("path/to/update/file.exe")
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// if update.is_newer_than_file("path/to/installed/file.exe", AbsentVersion::Oldest)
+    ///     .expect("Failed to retrieve version information") {
+    ///     println!("The update is newer than the installed file");
+    /// }
+    /// ```
+    pub fn is_newer_than_file<P: AsRef<Path>>(
+        &self,
+        other: P,
+        absent: AbsentVersion) -> Result<bool> {
+        let other = other.as_ref();
+        let other_version = match VersionInfoBlock::from_file(other)
+            .and_then(|block| block.get_fixed_file_info()) {
+            Ok(fixed_file_info) => fixed_file_info.map(|fixed_file_info| fixed_file_info.file_version),
+            Err(err) if matches!(err.kind(), ErrorKind::NoVersionInfo) => None,
+            Err(err) => return Err(err),
+        };
+        let version = self.fixed_file_info.map(|fixed_file_info| fixed_file_info.file_version);
+        match (absent, version, other_version) {
+            (AbsentVersion::Error, None, _) =>
+                Err(Error::new(ErrorKind::NoVersionInfo)),
+            (AbsentVersion::Error, _, None) =>
+                Err(Error::new(ErrorKind::NoVersionInfo).with_path(other)),
+            // `None` is ordered before any `Some`
+            _ => Ok(version > other_version),
+        }
+    }
+
+    /// Same as [`VersionInfo::fields`], following the naming convention of
+    /// collections.
+    #[must_use]
+    pub fn iter(&self) -> Fields<'_> {
+        self.fields()
+    }
+
+    /// Fills [`VersionInfo::file_version`] and [`VersionInfo::product_version`]
+    /// from the numeric versions of [`VersionInfo::fixed_file_info`] if they
+    /// are empty, so that code reading only the strings still finds them.
+    ///
+    /// See [`VersionInfoOs::merge_with_fixed`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::{FileVersion, FixedFileInfo, VersionInfo};
+    ///
+    /// let mut fixed_file_info = FixedFileInfo::default();
+    /// fixed_file_info.file_version = FileVersion::new(1, 2, 3, 4);
+    /// let mut info = VersionInfo::default();
+    /// info.fixed_file_info = Some(fixed_file_info);
+    ///
+    /// info.merge_with_fixed();
+    /// assert_eq!(info.file_version, "1.2.3.4");
+    /// // the numeric product version is `0.0.0.0`, i.e. missing
+    /// assert_eq!(info.product_version, "");
+    /// ```
+    pub fn merge_with_fixed(&mut self) {
+        let Some(fixed_file_info) = self.fixed_file_info else {
+            return;
+        };
+        for (field, version) in [
+            (&mut self.file_version, fixed_file_info.file_version),
+            (&mut self.product_version, fixed_file_info.product_version),
+        ] {
+            if field.is_empty() && version != FileVersion::default() {
+                *field = version.to_string();
+            }
+        }
+    }
+
+    /// Returns [`VersionInfo::private_build`] only if the file is flagged as a
+    /// private build, see [`FixedFileInfo::is_private_build`].
+    ///
+    /// The string is only defined by Win32 if the flag is set, so it may be
+    /// stale otherwise. Returns `None` if the file has no fixed version
+    /// information.
+    #[must_use]
+    pub fn private_build_if_flagged(&self) -> Option<&str> {
+        self.fixed_file_info
+            .is_some_and(|fixed_file_info| fixed_file_info.is_private_build())
+            .then_some(self.private_build.as_str())
+    }
+
+    /// Retrieves only the product name and the numeric product version of the
+    /// specified file, e.g. for listing products.
+    ///
+    /// The product name is [`VersionInfo::product_name`] of the translation
+    /// selected as in [`VersionInfo::from_file`], and the product version is
+    /// that of [`VersionInfo::fixed_file_info`], or `None` if the file has no
+    /// fixed version information.
+    ///
+    /// The whole version information block is still read from the file, but
+    /// only `FileVersion` is looked up in each translation tried and
+    /// `ProductName` in the selected one, rather than all twelve string
+    /// fields, which adds up when reading many files.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::VersionInfo;
+    ///
+    /// let (product_name, product_version) = VersionInfo::product_only("path/to/your/file.exe")
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// println!("Product name: {product_name}");
+    /// if let Some(product_version) = product_version {
+    ///     println!("Product version: {product_version}");
+    /// }
+    /// ```
+    pub fn product_only<P: AsRef<Path>>(file_name: P) -> Result<(String, Option<FileVersion>)> {
+        let ver_data = VersionInfoBlock::from_file(file_name)?;
+        VersionInfoBuilder::new()
+            .read_product(&ver_data)
+            .map(|(product_name, product_version)| (
+                product_name.to_string_lossy().into_owned(),
+                product_version))
+    }
+
+    /// Checks whether two files are the same build, comparing only the fields
+    /// identifying a build, i.e. those hashed by [`VersionInfo::hash_identity`].
+    ///
+    /// Unlike `==`, which compares all string fields, this ignores e.g.
+    /// [`VersionInfo::comments`] and [`VersionInfo::file_description`], which
+    /// may differ between localized copies of the same build. Files that are
+    /// the same build always have the same [`VersionInfo::hash_identity`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::{FileVersion, FixedFileInfo, VersionInfo};
+    ///
+    /// let mut english = VersionInfo::default();
+    /// english.company_name = "Contoso".to_owned();
+    /// english.file_description = "Widget Editor".to_owned();
+    /// let mut german = english.clone();
+    /// german.file_description = "Widget-Editor".to_owned();
+    /// assert!(english.same_build(&german));
+    /// assert_ne!(english, german);
+    ///
+    /// // the numeric versions identify the build, whatever the strings say
+    /// let mut fixed = FixedFileInfo::default();
+    /// fixed.product_version = FileVersion::new(1, 0, 0, 0);
+    /// english.fixed_file_info = Some(fixed);
+    /// english.product_version = "1.0".to_owned();
+    /// let mut padded = english.clone();
+    /// padded.product_version = "1.0.0.0".to_owned();
+    /// assert!(english.same_build(&padded));
+    ///
+    /// // but not if they are the 0.0.0.0 placeholder
+    /// let mut old = VersionInfo::default();
+    /// old.fixed_file_info = Some(FixedFileInfo::default());
+    /// old.product_version = "1.0".to_owned();
+    /// let mut new = old.clone();
+    /// new.product_version = "2.0".to_owned();
+    /// assert!(!old.same_build(&new));
+    /// ```
+    #[must_use]
+    pub fn same_build(&self, other: &Self) -> bool {
+        self.identity() == other.identity()
+    }
+
+    /// Returns [`VersionInfo::special_build`] only if the file is flagged as a
+    /// special build, see [`FixedFileInfo::is_special_build`].
+    ///
+    /// The string is only defined by Win32 if the flag is set, so it may be
+    /// stale otherwise. Returns `None` if the file has no fixed version
+    /// information.
+    #[must_use]
+    pub fn special_build_if_flagged(&self) -> Option<&str> {
+        self.fixed_file_info
+            .is_some_and(|fixed_file_info| fixed_file_info.is_special_build())
+            .then_some(self.special_build.as_str())
+    }
+
+    /// Picks the fields most useful to show to a user, as `(name, version,
+    /// company)`.
+    ///
+    /// - The name is [`VersionInfo::product_name`], falling back to
+    ///   [`VersionInfo::file_description`] if empty.
+    /// - The version is the binary file version from
+    ///   [`VersionInfo::fixed_file_info`] as `major.minor.build.revision`,
+    ///   falling back to [`VersionInfo::file_version`] if there is none or
+    ///   it is `0.0.0.0`.
+    /// - The company is [`VersionInfo::company_name`].
+    ///
+    /// Any of them may be empty if the file doesn't specify it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::VersionInfo;
+    ///
+    /// let mut info = VersionInfo::default();
+    /// info.company_name = "Contoso".to_owned();
+    /// info.file_description = "Contoso Widget".to_owned();
+    /// info.file_version = "1.2".to_owned();
+    /// assert_eq!(
+    ///     info.summary(),
+    ///     ("Contoso Widget".to_owned(), "1.2".to_owned(), "Contoso".to_owned()));
+    /// ```
+    #[must_use]
+    pub fn summary(&self) -> (String, String, String) {
+        let name = if self.product_name.is_empty() {
+            &self.file_description
+        } else {
+            &self.product_name
+        };
+        let version = self.fixed_file_info
+            .map(|fixed_file_info| fixed_file_info.file_version)
+            .filter(|file_version| *file_version != FileVersion::default())
+            .map_or_else(|| self.file_version.clone(), |file_version| file_version.to_string());
+        (name.clone(), version, self.company_name.clone())
+    }
+
+    /// Flattens the version information into `(name, value)` rows, e.g. for
+    /// writing to a CSV file.
+    ///
+    /// The rows are the string fields as in [`VersionInfo::fields`], followed,
+    /// if the file has fixed version information, by `FixedFileVersion` and
+    /// `FixedProductVersion` as `major.minor.build.revision` and `FileFlags`
+    /// as the valid bits of the flags in hexadecimal, e.g. `0x00000020`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::VersionInfo;
+    ///
+    /// let info = VersionInfo::from_file("path/to/your/file.exe")
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// for (name, value) in info.to_pairs() {
+    ///     println!("{name},{value:?}");
+    /// }
+    /// ```
+    #[must_use]
+    pub fn to_pairs(&self) -> Vec<(String, String)> {
+        let mut pairs = self.fields()
+            .map(|(name, value)| (name.to_owned(), value.to_owned()))
+            .collect::<Vec<_>>();
+        if let Some(fixed_file_info) = self.fixed_file_info {
+            pairs.extend([
+                ("FixedFileVersion".to_owned(), fixed_file_info.file_version.to_string()),
+                ("FixedProductVersion".to_owned(), fixed_file_info.product_version.to_string()),
+                ("FileFlags".to_owned(), format!("{:#010x}",
+                    fixed_file_info.file_flags & fixed_file_info.file_flags_mask)),
+            ]);
+        }
+        pairs
+    }
+
+    /// Checks whether the binary file version from
+    /// [`VersionInfo::fixed_file_info`] equals `expected`.
+    ///
+    /// Returns `false` if the file has no fixed version information.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::{FileVersion, VersionInfo};
+    ///
+    /// let info = VersionInfo::from_file("path/to/your/file.exe")
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// assert!(info.validate_against(FileVersion::new(1, 2, 3, 4)));
+    /// ```
+    #[must_use]
+    pub fn validate_against(&self, expected: FileVersion) -> bool {
+        self.fixed_file_info
+            .is_some_and(|fixed_file_info| fixed_file_info.file_version == expected)
+    }
+
+    /// Checks whether the binary product version from
+    /// [`VersionInfo::fixed_file_info`] equals `expected`.
+    ///
+    /// Returns `false` if the file has no fixed version information.
+    #[must_use]
+    pub fn validate_product(&self, expected: FileVersion) -> bool {
+        self.fixed_file_info
+            .is_some_and(|fixed_file_info| fixed_file_info.product_version == expected)
+    }
+
+    /// Checks whether the [`VersionInfo::file_version`] string matches the
+    /// numeric file version of [`VersionInfo::fixed_file_info`], e.g. to
+    /// catch a build where only one of them was bumped.
+    ///
+    /// Only the version number at the start of the string is compared, so
+    /// `10.0.19041.1 (WinBuild.160101.0800)` matches `10.0.19041.1`. Its parts
+    /// may be separated by dots or by commas, as in `1, 2, 3, 4`, and omitted
+    /// parts are zero, so `1.2` matches `1.2.0.0`.
+    ///
+    /// Returns `None` if the file has no fixed version information, or the
+    /// string is empty or doesn't start with a version number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::VersionInfo;
+    ///
+    /// let mut info = VersionInfo::default();
+    /// info.file_version = "1.2.3.4 (release)".to_owned();
+    /// assert_eq!(info.version_strings_consistent(), None);
+    /// ```
+    #[must_use]
+    pub fn version_strings_consistent(&self) -> Option<bool> {
+        let fixed_file_info = self.fixed_file_info?;
+        FileVersion::parse_string(&self.file_version)
+            .map(|file_version| file_version == fixed_file_info.file_version)
+    }
+}
+
+impl<'info> IntoIterator for &'info VersionInfo {
+    type IntoIter = Fields<'info>;
+
+    type Item = (&'static str, &'info str);
+
+    fn into_iter(self) -> Fields<'info> {
+        self.fields()
+    }
+}
+
+impl TryFrom<&Path> for VersionInfo {
+    type Error = Error;
+
+    /// Same as [`VersionInfo::from_file`].
+    fn try_from(file_name: &Path) -> Result<Self> {
+        Self::from_file(file_name)
+    }
+}
+
+impl TryFrom<PathBuf> for VersionInfo {
+    type Error = Error;
+
+    /// Same as [`VersionInfo::from_file`].
+    fn try_from(file_name: PathBuf) -> Result<Self> {
+        Self::from_file(file_name)
+    }
+}
+
+/// An iterator over the string fields of a [`VersionInfo`] as `(name, value)`
+/// pairs, returned by [`VersionInfo::fields`].
+#[derive(Debug, Clone)]
+pub struct Fields<'info>(
+    iter::Zip<iter::Copied<slice::Iter<'static, &'static str>>, array::IntoIter<&'info str, 12>>);
+
+impl<'info> Iterator for Fields<'info> {
+    type Item = (&'static str, &'info str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Fields<'_> {}
+
+/// Represents version information for a file.
+///
+/// This struct contains various fields that provide detailed information
+/// about the file, such as its description and version number, company that
+/// produced it, and other metadata.
+/// 
+/// This struct is similar to [`VersionInfo`], but it uses [`OsString`] instead
+/// of [`String`] for its string fields to preserve any possibly ill-formed
+/// UTF-16 data that may be present in the version information of the file.
+/// 
+/// String values in the `StringFileInfo` block of a 32-bit version resource are
+/// always stored as UTF-16, regardless of the code page declared by the
+/// translation they belong to. The code page only records the encoding the
+/// strings were originally authored in, so values are read as UTF-16 for every
+/// translation, including the fallbacks tried after the declared ones, i.e.
+/// `040904B0`, `040904E4` (Windows-1252), `04090000`, `000004B0` and
+/// `000004E4` in this order, see [`VersionInfoBuilder::disable_fallbacks`].
+/// 
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct VersionInfoOs {
+    /// The comments associated with the file.
+    pub comments: OsString,
+    /// The name of the company that produced the file.
+    pub company_name: OsString,
+    /// The description of the file.
+    pub file_description: OsString,
+    /// The file version number.
+    pub file_version: OsString,
+    /// The fixed, language-independent part of the version information, if
+    /// the file has one.
+    ///
+    /// It is read from the root block `\`, outside of any string table, so it
+    /// is the same whichever translation the string fields are read from.
+    pub fixed_file_info: Option<FixedFileInfo>,
+    /// The internal name of the file, if one exists.
+    pub internal_name: OsString,
+    /// The copyright notices that apply to the specified file.
+    pub legal_copyright: OsString,
+    /// The trademarks and registered trademarks that apply to the file.
+    pub legal_trademarks: OsString,
+    /// The name the file was created with.
+    pub original_filename: OsString,
+    /// The name of the product this file is distributed with.
+    pub product_name: OsString,
+    /// The version of the product this file is distributed with.
+    pub product_version: OsString,
+    /// The private build information for the file.
+    pub private_build: OsString,
+    /// The special build information for the file.
+    pub special_build: OsString,
+}
+
+impl VersionInfoOs {
+    /// Retrieves the string fields of every translation declared by the
+    /// specified file, as `(translation, version information)` pairs in the
+    /// order they are declared, e.g. for auditing localizations.
+    ///
+    /// Unlike [`VersionInfoOs::from_file`], no translation is selected and
+    /// there is no fallback to US English, so a file declaring no
+    /// translations results in an empty [`Vec`]. Each item carries the same
+    /// [`VersionInfoOs::fixed_file_info`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::VersionInfoOs;
+    ///
+    /// let infos = VersionInfoOs::all_translations_fields("path/to/your/file.exe")
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// for (translation, info) in infos {
+    ///     println!("{translation}: {}", info.file_description.to_string_lossy());
+    /// }
+    /// ```
+    pub fn all_translations_fields<P: AsRef<Path>>(file_name: P) -> Result<Vec<(Translation, Self)>> {
+        let builder = VersionInfoBuilder::new();
+        let ver_data = VersionInfoBlock::from_file(file_name.as_ref())?;
+        let fixed_file_info = ver_data.get_fixed_file_info()?;
+        ver_data.get_translations()?
+            .into_iter()
+            .map(|translation| {
+                let mut ver_info = Self {
+                    fixed_file_info,
+                    ..Self::default()
+                };
+                ver_data.get_all_fields_in_translation(translation, &builder, &mut ver_info)?;
+                Ok((translation, ver_info))
+            })
+            .collect()
+    }
+
+    /// Empties all string fields and removes
+    /// [`VersionInfoOs::fixed_file_info`], keeping the allocations of the
+    /// string fields for reuse.
+    pub fn clear(&mut self) {
+        for field in self.fields_mut() {
+            field.clear();
+        }
+        self.fixed_file_info = None;
+    }
+
+    /// Describes the structure of the version information block of the
+    /// specified file as a human-readable tree, e.g. to attach to bug reports
+    /// about files whose version information is read unexpectedly.
+    ///
+    /// Each line describes a node, indented by its depth: its key, the length
+    /// of its value in bytes, whether the value is text or binary, and the
+    /// value itself. The fields of the `VS_FIXEDFILEINFO` of the root node are
+    /// listed below it, and translations are shown as the keys of their
+    /// string tables. Nodes that don't lie within their parent are described
+    /// as malformed rather than failing, as such blocks are what bug reports
+    /// are most likely about. The format is meant for humans and may change.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::VersionInfoOs;
+    ///
+    /// let structure = VersionInfoOs::dump_structure("path/to/your/file.exe")
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// // VS_VERSION_INFO (52 bytes, binary): VS_FIXEDFILEINFO
+    /// //   dwSignature: 0xfeef04bd
+    /// //   ...
+    /// //   StringFileInfo (0 bytes, text)
+    /// //     040904b0 (0 bytes, text)
+    /// //       CompanyName (26 bytes, text): "Example Corp\0"
+    /// //   ...
+    /// //   VarFileInfo (0 bytes, binary)
+    /// //     Translation (4 bytes, binary): 040904b0
+    /// print!("{structure}");
+    /// ```
+    pub fn dump_structure<P: AsRef<Path>>(file_name: P) -> Result<String> {
+        VersionInfoBlock::from_file(file_name).map(|block| block.dump_structure())
+    }
+
+    /// References to the string fields, in the order of [`STANDARD_FIELDS`].
+    const fn field_values(&self) -> [&OsString; 12] {
+        [
+            &self.comments,
+            &self.company_name,
+            &self.file_description,
+            &self.file_version,
+            &self.internal_name,
+            &self.legal_copyright,
+            &self.legal_trademarks,
+            &self.original_filename,
+            &self.product_name,
+            &self.product_version,
+            &self.private_build,
+            &self.special_build,
+        ]
+    }
+
+    /// Iterates over the string fields as `(name, value)` pairs, in the order
+    /// of [`STANDARD_FIELDS`], preserving possibly ill-formed UTF-16 data.
+    ///
+    /// See [`VersionInfo::fields`] for details. `&VersionInfoOs` also
+    /// implements [`IntoIterator`] with the same items.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::VersionInfoOs;
+    ///
+    /// let info = VersionInfoOs::from_file("path/to/your/file.exe")
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// for (name, value) in info.fields() {
+    ///     println!("{name}: {}", value.to_string_lossy());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn fields(&self) -> FieldsOs<'_> {
+        FieldsOs(STANDARD_FIELDS.iter().copied().zip(self.field_values().map(OsString::as_os_str)))
+    }
+
+    /// Mutable references to the string fields, in the order of
+    /// [`STANDARD_FIELDS`].
+    const fn fields_mut(&mut self) -> [&mut OsString; 12] {
+        [
+            &mut self.comments,
+            &mut self.company_name,
+            &mut self.file_description,
+            &mut self.file_version,
+            &mut self.internal_name,
+            &mut self.legal_copyright,
+            &mut self.legal_trademarks,
+            &mut self.original_filename,
+            &mut self.product_name,
+            &mut self.product_version,
+            &mut self.private_build,
+            &mut self.special_build,
+        ]
+    }
+
+    /// Returns [`VersionInfoOs::file_description`], or
+    /// [`VersionInfoOs::original_filename`] if the file description is empty,
+    /// e.g. for displaying a name for the file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::VersionInfoOs;
+    ///
+    /// let mut info = VersionInfoOs::default();
+    /// info.original_filename = "widget.exe".into();
+    /// assert_eq!(info.file_description_or_original(), "widget.exe");
+    /// ```
+    #[must_use]
+    pub fn file_description_or_original(&self) -> &OsStr {
+        if self.file_description.is_empty() {
+            &self.original_filename
+        } else {
+            &self.file_description
+        }
+    }
+
+    /// Retrieves version information from a raw version information block
+    /// that has already been read, e.g. to also query it with
+    /// [`VersionInfoBlock::query_raw`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`ErrorKind::MalformedBlock`] if a value in the
+    /// block doesn't lie within it.
+    pub fn from_block(block: &VersionInfoBlock) -> Result<Self> {
+        VersionInfoBuilder::new().read_internal(block)
+    }
+
+    /// Retrieves version information from a raw version information block,
+    /// i.e. the data returned by `GetFileVersionInfoW` or the content of an
+    /// `RT_VERSION` resource.
+    ///
+    /// The block is validated before being read, so malformed data results in
+    /// an error rather than out-of-bounds reads.
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`ErrorKind::MalformedBlock`] if `data` is not a
+    /// well-formed version information block.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        VersionInfoBuilder::new().read_internal(&VersionInfoBlock::from_bytes(data)?)
+    }
+
+    /// Retrieves version information from the module containing this crate,
+    /// i.e. the DLL it is linked into, or the executable if it is linked into
+    /// one.
+    ///
+    /// The `RT_VERSION` resource is read from the module in memory, see
+    /// [`VersionInfoBlock::from_module`]. This is the recommended way to read
+    /// version information from within a DLL loaded by a host that may not be
+    /// written in Rust, as it neither resolves nor opens the file of the
+    /// module. Reading files by path instead depends on state of the host
+    /// process, e.g. relative paths are resolved against its current
+    /// directory.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The module has no version information, see [`ErrorKind::NoVersionInfo`].
+    /// - The version resource is malformed, see [`ErrorKind::MalformedBlock`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::VersionInfoOs;
+    ///
+    /// // in a DLL, the version of the DLL rather than of its host
+    /// let info = VersionInfoOs::from_current_module()
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// println!("Plugin version: {}", info.file_version.to_string_lossy());
+    /// ```
+    pub fn from_current_module() -> Result<Self> {
+        Self::from_block(&VersionInfoBlock::from_current_module()?)
+    }
+
+    /// Retrieves version information from the executable of the current
+    /// process, e.g. the host application of a DLL.
+    ///
+    /// The `RT_VERSION` resource is read from the executable in memory, see
+    /// [`VersionInfoBlock::from_module`], so this doesn't depend on the path
+    /// the process was started from.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The executable has no version information, see
+    ///   [`ErrorKind::NoVersionInfo`].
+    /// - The version resource is malformed, see [`ErrorKind::MalformedBlock`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::VersionInfoOs;
+    ///
+    /// let host = VersionInfoOs::from_executable_module()
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// println!("Host version: {}", host.product_version.to_string_lossy());
+    /// ```
+    pub fn from_executable_module() -> Result<Self> {
+        Self::from_block(&VersionInfoBlock::from_executable_module()?)
+    }
+
+    /// Creates version information from string fields keyed by their names as
+    /// in [`STANDARD_FIELDS`], e.g. to test code taking [`VersionInfoOs`]
+    /// without reading real files.
+    ///
+    /// Fields missing from `fields` are left empty, and names not in
+    /// [`STANDARD_FIELDS`] are ignored. [`VersionInfoOs::fixed_file_info`] is
+    /// `None`, and can be set afterwards.
+    ///
+    /// This function requires the `mock` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use win32_version_info::VersionInfoOs;
+    ///
+    /// let info = VersionInfoOs::from_fields(BTreeMap::from([
+    ///     ("CompanyName", "Contoso".into()),
+    ///     ("FileVersion", "1.2.3.4".into()),
+    ///     ("BuildHost", "ci-42".into()),
+    /// ]));
+    /// assert_eq!(info.company_name, "Contoso");
+    /// assert_eq!(info.file_version, "1.2.3.4");
+    /// assert!(info.comments.is_empty());
+    /// ```
+    #[cfg(feature = "mock")]
+    #[must_use]
+    pub fn from_fields(mut fields: BTreeMap<&str, OsString>) -> Self {
+        Self::from_lookup(|name| fields.remove(name))
+    }
+
+    /// Retrieves version information from the specified file.
+    /// 
+    /// This function is similar to [`VersionInfo::from_file`], but it uses
+    /// [`OsString`] instead of [`String`] for its string fields to preserve any
+    /// possibly ill-formed UTF-16 data that may be present in the version
+    /// information of the file.
+    /// 
+    /// # Errors
+    /// 
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    /// - The file is a legacy 16-bit executable, see
+    ///   [`ErrorKind::LegacyExecutableUnsupported`].
+    /// - The file is a Windows Installer package, see
+    ///   [`ErrorKind::UnsupportedFormat`].
+    /// - The path is too long, see [`ErrorKind::PathTooLong`].
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use your_crate_name::VersionInfoOs;
+    /// 
+    /// let info = VersionInfoOs::from_file("path/to/your/file.exe")
+    ///    .expect("Failed to retrieve version information");
+    /// 
+    /// println!("File description: {}", info.file_description.to_string_lossy());
+    /// println!("File version: {}", info.file_version.to_string_lossy());
+    /// ```
+    /// 
+    pub fn from_file<P: AsRef<Path>>(file_name: P) -> Result<Self> {
+        VersionInfoBuilder::new().read_os(file_name)
+    }
+
+    /// Retrieves version information from the specified file, whose path is
+    /// encoded in the ANSI code page, with `GetFileVersionInfoSizeExA` and
+    /// `GetFileVersionInfoExA`.
+    ///
+    /// This is for exact compatibility with legacy callers passing paths in
+    /// the ANSI code page of the system, i.e. `CP_ACP`, which depends on the
+    /// system locale. Paths containing characters outside that code page
+    /// cannot be represented at all, so prefer [`VersionInfoOs::from_file`]
+    /// whenever the path is available as a [`Path`].
+    ///
+    /// Unlike [`VersionInfoOs::from_file`], the path is not canonicalized
+    /// and retried if the file is not found. The path carried by an error is
+    /// decoded from the ANSI code page.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::VersionInfoOs;
+    ///
+    /// let info = VersionInfoOs::from_file_ansi(c"path/to/your/file.exe")
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// println!("File version: {}", info.file_version.to_string_lossy());
+    /// ```
+    pub fn from_file_ansi(file_name: &ffi::CStr) -> Result<Self> {
+        VersionInfoBuilder::new().read_internal(&VersionInfoBlock::read_file_ansi(file_name)?)
+    }
+
+    /// Retrieves version information from the file at a path relative to an
+    /// open directory, e.g. one opened by `NtCreateFile` or
+    /// [`File::open`] with `FILE_FLAG_BACKUP_SEMANTICS`.
+    ///
+    /// `GetFileVersionInfoExW` only accepts paths, so the full path of the
+    /// directory is first resolved from its handle with
+    /// `GetFinalPathNameByHandleW`, as a normalized `\\?\` path with the
+    /// drive letter of its volume. `file_name` is then appended to it, and
+    /// the version information is read from the resulting path exactly as
+    /// [`VersionInfoOs::from_file`] does.
+    ///
+    /// `file_name` must be a relative path without `..` components, so the
+    /// file is always looked up inside the directory. Note that the file is
+    /// still opened by its path, so this pins the directory, even if it is
+    /// renamed or moved after being opened, but not the directories or links
+    /// below it. To read an already opened file without going through its
+    /// path at all, see [`VersionInfoOs::from_pe_reader`].
+    ///
+    /// Resolving the path requires no privileges beyond holding the handle.
+    /// Reading the file requires read access to it, as for
+    /// [`VersionInfoOs::from_file`], and is checked against the token of the
+    /// calling thread rather than the access the directory was opened with.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - `file_name` is absolute or has a `..` component, see
+    ///   [`io::ErrorKind::InvalidInput`].
+    /// - The path of the directory cannot be resolved from its handle.
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::OpenOptions;
+    /// use std::os::windows::fs::OpenOptionsExt;
+    /// use win32_version_info::VersionInfoOs;
+    ///
+    /// const FILE_FLAG_BACKUP_SEMANTICS: u32 = 0x02000000;
+    ///
+    /// let dir = OpenOptions::new()
+    ///     .read(true)
+    ///     .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+    ///     .open("path/to/your/dir")
+    ///     .expect("Failed to open the directory");
+    /// let info = VersionInfoOs::from_file_at(&dir, "bin/file.exe")
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// println!("File version: {}", info.file_version.to_string_lossy());
+    /// ```
+    pub fn from_file_at<H: AsHandle, P: AsRef<Path>>(dir: H, file_name: P) -> Result<Self> {
+        Self::from_file(path_at(dir.as_handle(), file_name.as_ref())?)
+    }
+
+    /// Retrieves version information from the specified file, preferring the
+    /// translation in the UI language of the current thread.
+    ///
+    /// The language is queried with `GetThreadUILanguage`, which returns the
+    /// language set by `SetThreadUILanguage` if any, and the UI language of
+    /// the user otherwise. `GetUserDefaultUILanguage` is used as a fallback if
+    /// the former fails. The translations declared by the file in that
+    /// language are then tried first, see [`VersionInfoBuilder::language`].
+    ///
+    /// If the file has no translation in that language, this function returns
+    /// the same as [`VersionInfoOs::from_file`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    pub fn from_file_current_locale<P: AsRef<Path>>(file_name: P) -> Result<Self> {
+        VersionInfoBuilder::new()
+            .language(Some(sys::current_ui_language()))
+            .read_os(file_name)
+    }
+
+    /// Retrieves both the language-neutral and the localized version
+    /// information of the specified file, as `(neutral, localized)`.
+    ///
+    /// Since Windows Vista, the version resource of a file may be split into a
+    /// language-neutral part in the file itself and localized parts in its MUI
+    /// (Multilingual User Interface) resource files. This function reads both
+    /// with `GetFileVersionInfoExW`, using `FILE_VER_GET_NEUTRAL` and
+    /// `FILE_VER_GET_LOCALISED` respectively, making the distinction explicit.
+    /// For files without MUI resources, both are the same.
+    ///
+    /// Since a single `GetFileVersionInfoExW` call returns only one of them,
+    /// this function calls it twice, once with each flag. Use
+    /// [`VersionInfoBuilder::read_dual_os`] to read both with other options.
+    ///
+    /// [`VersionInfoOs::from_file`] returns the localized version information.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    pub fn from_file_dual<P: AsRef<Path>>(file_name: P) -> Result<(Self, Self)> {
+        VersionInfoBuilder::new().read_dual_os(file_name)
+    }
+
+    /// Retrieves the string fields of exactly the specified translation of
+    /// the specified file, e.g. to read its Unicode string table rather than
+    /// an ANSI one in the same language.
+    ///
+    /// The language ID and the code page must both match a string table of
+    /// the file, whether or not the file declares the translation. Unlike
+    /// [`VersionInfoBuilder::translation`], there is no fallback to other
+    /// translations, even if fields of the string table are empty. Use
+    /// [`VersionInfoBuilder::translation_exact`] to fall back to the other
+    /// translations if the file has no such string table, or to read with
+    /// other options.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    /// - The file has no string table for `translation`, see
+    ///   [`ErrorKind::TranslationNotFound`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::{Translation, VersionInfoOs};
+    ///
+    /// // US English in Windows-1252 rather than in UTF-16
+    /// let info = VersionInfoOs::from_file_with_translation(
+    ///     "path/to/your/file.exe",
+    ///     Translation::new(0x0409, 0x04E4))
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// println!("File description: {}", info.file_description.to_string_lossy());
+    /// ```
+    pub fn from_file_with_translation<P: AsRef<Path>>(
+        file_name: P,
+        translation: Translation) -> Result<Self> {
+        VersionInfoBuilder::new()
+            .translation_exact(Some(translation))
+            .disable_fallbacks(true)
+            .read_os(file_name)
+    }
+
+    /// Creates version information whose string fields are looked up by their
+    /// names in [`STANDARD_FIELDS`], leaving those `lookup` returns `None` for
+    /// empty.
+    fn from_lookup(mut lookup: impl FnMut(&str) -> Option<OsString>) -> Self {
+        let mut info = Self::default();
+        for (name, field) in STANDARD_FIELDS.iter().zip(info.fields_mut()) {
+            if let Some(value) = lookup(name) {
+                *field = value;
+            }
+        }
+        info
+    }
+
+    /// Retrieves version information from a PE image in memory, e.g. the full
+    /// content of an executable file or a memory mapping of it.
+    ///
+    /// The `RT_VERSION` resource is located by parsing the PE image directly,
+    /// without going through the loader, and then read with
+    /// [`VersionInfoOs::from_bytes`]. Images without resources, e.g. without
+    /// a `.rsrc` section, fail with [`ErrorKind::NoVersionInfo`] just like
+    /// those whose resources have no version resource.
+    ///
+    /// To read a member of an archive, e.g. a CAB or ZIP file, without
+    /// extracting it to disk, decompress the member into memory with a crate
+    /// for the archive format and pass its bytes to this function.
+    /// Decompression is out of the scope of this crate.
+    ///
+    /// This function requires the `pe-parser` feature.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - `image` is not a valid PE image, see [`ErrorKind::InvalidPeImage`].
+    /// - The image has no version resource, see [`ErrorKind::NoVersionInfo`].
+    /// - The version resource is malformed, see [`ErrorKind::MalformedBlock`].
+    #[cfg(feature = "pe-parser")]
+    pub fn from_pe_image(image: &[u8]) -> Result<Self> {
+        pe::PeImage::parse(image)?
+            .version_resource()?
+            .ok_or(Error::new(ErrorKind::NoVersionInfo))
+            .and_then(Self::from_bytes)
+    }
+
+    /// Retrieves version information from a PE image starting at `offset`
+    /// within `data`, e.g. one of several executables concatenated in a
+    /// container or appended to a self-extracting stub.
+    ///
+    /// The image must start with the `MZ` signature exactly at `offset`, and
+    /// its `PE` signature and all other offsets are taken relative to it. It
+    /// is then read as with [`VersionInfoOs::from_pe_image`], so it may extend
+    /// to the end of `data`.
+    ///
+    /// This function requires the `pe-parser` feature.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - There is no valid PE image at `offset`, e.g. the `MZ` or `PE`
+    ///   signature is missing or `offset` is past the end of `data`, see
+    ///   [`ErrorKind::InvalidPeImage`].
+    /// - The image has no version resource, see [`ErrorKind::NoVersionInfo`].
+    /// - The version resource is malformed, see [`ErrorKind::MalformedBlock`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::VersionInfoOs;
+    ///
+    /// let archive = std::fs::read("path/to/your/archive.bin")
+    ///     .expect("Failed to read the archive");
+    /// let info = VersionInfoOs::from_pe_image_at(&archive, 0x1000)
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// println!("File version: {}", info.file_version.to_string_lossy());
+    /// ```
+    #[cfg(feature = "pe-parser")]
+    pub fn from_pe_image_at(data: &[u8], offset: usize) -> Result<Self> {
+        data.get(offset..)
+            .ok_or(Error::new(ErrorKind::InvalidPeImage))
+            .and_then(Self::from_pe_image)
+    }
+
+    /// Retrieves version information from a PE image read from `reader`,
+    /// starting at its current position, e.g. a [`File`] or a
+    /// [`Cursor`](std::io::Cursor) positioned at the image.
+    ///
+    /// Unlike [`VersionInfoOs::from_pe_image`], the image is not loaded into
+    /// memory as a whole. Only its headers, its resource table and the
+    /// `RT_VERSION` resource are read, seeking to each of them in turn, which
+    /// suits large images, e.g. inside archives. The resource is then read
+    /// with [`VersionInfoOs::from_bytes`].
+    ///
+    /// This function requires the `pe-parser` feature.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - Reading or seeking fails.
+    /// - The image is not a valid PE image, see [`ErrorKind::InvalidPeImage`].
+    /// - The image has no version resource, see [`ErrorKind::NoVersionInfo`].
+    /// - The version resource is malformed, see [`ErrorKind::MalformedBlock`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    /// use win32_version_info::VersionInfoOs;
+    ///
+    /// let file = File::open("path/to/your/file.exe")
+    ///     .expect("Failed to open the file");
+    /// let info = VersionInfoOs::from_pe_reader(file)
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// println!("File version: {}", info.file_version.to_string_lossy());
+    /// ```
+    #[cfg(feature = "pe-parser")]
+    pub fn from_pe_reader<R: io::Read + io::Seek>(mut reader: R) -> Result<Self> {
+        let data = pe::read_version_resource(&mut reader)?
+            .ok_or(Error::new(ErrorKind::NoVersionInfo))?;
+        Self::from_bytes(&data)
+    }
+
+    /// Retrieves version information from the main executable image of the
+    /// process with the specified process ID.
+    ///
+    /// The process is opened with `PROCESS_QUERY_LIMITED_INFORMATION` and the
+    /// full path of its image is resolved with `QueryFullProcessImageNameW`.
+    /// The version information is then read from that file on disk, exactly
+    /// as [`VersionInfoOs::from_file`] does.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The process does not exist.
+    /// - The process cannot be opened, e.g. it is a protected process. In this
+    ///   case the error is [`ErrorKind::ProcessAccessDenied`] with the process
+    ///   ID.
+    /// - The version information cannot be retrieved.
+    pub fn from_process_id(pid: u32) -> Result<Self> {
+        Self::from_file(process_image_path(pid)?)
+    }
+
+    /// Creates version information from string fields given as raw UTF-16
+    /// code units, as returned by [`VersionInfoOs::to_wide_map`].
+    ///
+    /// Fields missing from `map` are left empty, and names not in
+    /// [`STANDARD_FIELDS`] are ignored. [`VersionInfoOs::fixed_file_info`] is
+    /// `None`.
+    #[must_use]
+    pub fn from_wide_map(map: &BTreeMap<&str, Vec<u16>>) -> Self {
+        Self::from_lookup(|name| map.get(name).map(|value| OsString::from_wide(value)))
+    }
+
+    /// Returns `true` if all string fields are empty, i.e. the version
+    /// resource has no usable string table.
+    ///
+    /// See [`VersionInfo::is_empty`] for details.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.field_values().iter().all(|value| value.is_empty())
+    }
+
+    /// Same as [`VersionInfoOs::fields`], following the naming convention of
+    /// collections.
+    #[must_use]
+    pub fn iter(&self) -> FieldsOs<'_> {
+        self.fields()
+    }
+
+    /// Fills each empty field with the corresponding field of `other`, e.g. to
+    /// combine the version information of a binary with that of its MUI
+    /// resource file.
+    ///
+    /// Non-empty fields are never overwritten, so the result prefers `self`
+    /// field by field. [`VersionInfoOs::fixed_file_info`] is taken from
+    /// `other` only if `self` has none.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::VersionInfoOs;
+    ///
+    /// let mut info = VersionInfoOs::from_file("path/to/your/file.dll")
+    ///     .expect("Failed to retrieve version information");
+    /// let mui = VersionInfoOs::from_file("path/to/your/en-US/file.dll.mui")
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// info.merge_empty_from(&mui);
+    /// ```
+    pub fn merge_empty_from(&mut self, other: &Self) {
+        for (field, other) in self.fields_mut().into_iter().zip(other.field_values()) {
+            if field.is_empty() {
+                field.clone_from(other);
+            }
+        }
+        if self.fixed_file_info.is_none() {
+            self.fixed_file_info = other.fixed_file_info;
+        }
+    }
+
+    /// Fills [`VersionInfoOs::file_version`] and
+    /// [`VersionInfoOs::product_version`] from the numeric versions of
+    /// [`VersionInfoOs::fixed_file_info`] if they are empty, e.g. for files
+    /// that only set the numeric versions.
+    ///
+    /// The numeric versions are formatted as `major.minor.build.revision`.
+    /// Non-empty fields are never overwritten, and a numeric version of
+    /// `0.0.0.0` is treated as missing, as in
+    /// [`VersionInfo::best_file_version`].
+    ///
+    /// [`VersionInfoBuilder::fill_strings_from_fixed`] does this when reading.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::VersionInfoOs;
+    ///
+    /// let mut info = VersionInfoOs::from_file("path/to/your/file.exe")
+    ///     .expect("Failed to retrieve version information");
+    /// info.merge_with_fixed();
+    ///
+    /// println!("File version: {}", info.file_version.to_string_lossy());
+    /// ```
+    pub fn merge_with_fixed(&mut self) {
+        let Some(fixed_file_info) = self.fixed_file_info else {
+            return;
+        };
+        for (field, version) in [
+            (&mut self.file_version, fixed_file_info.file_version),
+            (&mut self.product_version, fixed_file_info.product_version),
+        ] {
+            if field.is_empty() && version != FileVersion::default() {
+                *field = version.to_string().into();
+            }
+        }
+    }
+
+    /// Returns the string fields as their raw UTF-16 code units, keyed by
+    /// their names as in [`STANDARD_FIELDS`], e.g. to store them in a
+    /// database.
+    ///
+    /// Unlike converting the fields to [`String`], this preserves ill-formed
+    /// UTF-16 data, so [`VersionInfoOs::from_wide_map`] restores exactly the
+    /// same string fields. [`VersionInfoOs::fixed_file_info`] is not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::VersionInfoOs;
+    ///
+    /// let mut info = VersionInfoOs::default();
+    /// info.file_description = "Widget".into();
+    ///
+    /// let map = info.to_wide_map();
+    /// assert_eq!(map["FileDescription"], "Widget".encode_utf16().collect::<Vec<_>>());
+    /// assert_eq!(VersionInfoOs::from_wide_map(&map), info);
+    /// ```
+    #[must_use]
+    pub fn to_wide_map(&self) -> BTreeMap<&'static str, Vec<u16>> {
+        STANDARD_FIELDS
+            .iter()
+            .copied()
+            .zip(self.field_values())
+            .map(|(name, value)| (name, value.encode_wide().collect()))
+            .collect()
+    }
+}
+
+impl<'info> IntoIterator for &'info VersionInfoOs {
+    type IntoIter = FieldsOs<'info>;
+
+    type Item = (&'static str, &'info OsStr);
+
+    fn into_iter(self) -> FieldsOs<'info> {
+        self.fields()
+    }
+}
+
+impl TryFrom<&Path> for VersionInfoOs {
+    type Error = Error;
+
+    /// Same as [`VersionInfoOs::from_file`].
+    fn try_from(file_name: &Path) -> Result<Self> {
+        Self::from_file(file_name)
+    }
+}
+
+impl TryFrom<PathBuf> for VersionInfoOs {
+    type Error = Error;
+
+    /// Same as [`VersionInfoOs::from_file`].
+    fn try_from(file_name: PathBuf) -> Result<Self> {
+        Self::from_file(file_name)
+    }
+}
+
+/// An iterator over the string fields of a [`VersionInfoOs`] as `(name,
+/// value)` pairs, returned by [`VersionInfoOs::fields`].
+#[derive(Debug, Clone)]
+pub struct FieldsOs<'info>(
+    iter::Zip<iter::Copied<slice::Iter<'static, &'static str>>, array::IntoIter<&'info OsStr, 12>>);
+
+impl<'info> Iterator for FieldsOs<'info> {
+    type Item = (&'static str, &'info OsStr);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for FieldsOs<'_> {}
+
+/// Controls how [`VersionInfo::is_newer_than_file`] handles a file without
+/// fixed version information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum AbsentVersion {
+    /// Fails with [`ErrorKind::NoVersionInfo`]. This is the default.
+    #[default]
+    Error,
+    /// Treats the missing version as older than any other, so a file without
+    /// a version is never newer than one with a version.
+    Oldest,
+}
+
+/// Controls how trailing NUL characters are removed from string values.
+///
+/// String values in the version information are usually, but not always,
+/// NUL-terminated, and some files pad them with additional NULs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum TrailingNul {
+    /// Keeps string values exactly as they are stored.
+    Keep,
+    /// Strips all trailing NULs. This is the default.
+    #[default]
+    StripAll,
+    /// Strips at most one trailing NUL, i.e. the terminator.
+    StripOne,
+}
+
+/// Controls how ill-formed UTF-16 data in string values, i.e. unpaired
+/// surrogates, is converted when building the [`String`] fields of
+/// [`VersionInfo`].
+///
+/// [`VersionInfoOs`] preserves such data as is, so it is unaffected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum InvalidUtf16Policy {
+    /// Drops unpaired surrogates.
+    Drop,
+    /// Replaces each unpaired surrogate with the Unicode replacement
+    /// character (�), as [`OsStr::to_string_lossy`] does. This is the default.
+    #[default]
+    Replace,
+    /// Replaces each unpaired surrogate with the given character, e.g. `?`.
+    Substitute(char),
+}
+
+impl InvalidUtf16Policy {
+    fn decode(self, value: &OsStr) -> String {
+        let substitute = match self {
+            Self::Replace => return value.to_string_lossy().into_owned(),
+            Self::Drop => None,
+            Self::Substitute(substitute) => Some(substitute),
+        };
+        char::decode_utf16(value.encode_wide())
+            .filter_map(|unit| unit.ok().or(substitute))
+            .collect()
+    }
+}
+
+/// A builder for retrieving version information with non-default options.
+///
+/// [`VersionInfo::from_file`] and [`VersionInfoOs::from_file`] are equivalent
+/// to reading with a builder with all options left at their defaults.
+///
+/// # Examples
+///
+/// ```no_run
+/// use win32_version_info::{TrailingNul, VersionInfoBuilder};
+///
+/// let info = VersionInfoBuilder::new()
+///     .trailing_nul(TrailingNul::Keep)
+///     .read("path/to/your/file.exe")
+///     .expect("Failed to retrieve version information");
+///
+/// println!("File description: {:?}", info.file_description);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[must_use]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each flag is an independent option set by its own builder method",
+)]
+pub struct VersionInfoBuilder {
+    #[cfg(feature = "pe-parser")]
+    bypass_loader: bool,
+    cancel_flag: CancelFlag,
+    disable_fallbacks: bool,
+    fill_strings_from_fixed: bool,
+    invalid_utf16_policy: InvalidUtf16Policy,
+    language: Option<u16>,
+    merge_translations: bool,
+    neutral: bool,
+    parallelism: Option<usize>,
+    resolve_symlinks: bool,
+    sharing_violation_retries: u32,
+    trailing_nul: TrailingNul,
+    translation: Option<Translation>,
+    translation_exact: Option<Translation>,
+    trim_whitespace: bool,
+}
+
+/// A cancellation flag that is compared and hashed by identity, so that
+/// [`VersionInfoBuilder`] can keep deriving its traits.
+#[derive(Debug, Clone, Default)]
+struct CancelFlag(Option<Arc<AtomicBool>>);
+
+impl CancelFlag {
+    fn is_set(&self) -> bool {
+        self.0.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed))
+    }
+}
+
+impl PartialEq for CancelFlag {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (Some(flag), Some(other)) => Arc::ptr_eq(flag, other),
+            (None, None) => true,
+            (Some(_), None) | (None, Some(_)) => false,
+        }
+    }
+}
+
+impl Eq for CancelFlag {}
+
+impl Hash for CancelFlag {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.as_ref().map(Arc::as_ptr).hash(state);
+    }
+}
+
+impl VersionInfoBuilder {
+    /// Sets whether to read the `RT_VERSION` resource of the file directly,
+    /// bypassing the loader.
+    ///
+    /// `GetFileVersionInfoExW` loads the file as a resource module, so the
+    /// version information read may be affected by the loader, e.g. by the
+    /// activation context of the calling process or side-by-side assembly
+    /// redirection, and includes the localized parts from MUI resource files.
+    /// When bypassing the loader, the file is instead parsed as a PE image as
+    /// in [`VersionInfoOs::from_pe_reader`], so the version information is
+    /// exactly the literal resource in the file, independent of the manifest
+    /// of the caller.
+    ///
+    /// This option requires the `pe-parser` feature.
+    ///
+    /// Defaults to `false`.
+    #[cfg(feature = "pe-parser")]
+    pub const fn bypass_loader(mut self, bypass_loader: bool) -> Self {
+        self.bypass_loader = bypass_loader;
+        self
+    }
+
+    /// Sets a flag to cancel [`VersionInfoBuilder::read_batch`],
+    /// [`VersionInfoBuilder::scan_dir_filtered`] and, with the `glob` feature,
+    /// `VersionInfoBuilder::read_glob`, e.g. from the UI thread of a GUI tool
+    /// when the user aborts a long scan.
+    ///
+    /// The flag is checked before reading each file. Once it is set,
+    /// `read_batch` returns the results of the files read so far, and the
+    /// iterators of the others end. Reading a file that has already started
+    /// is not interrupted, as `GetFileVersionInfoExW` can't be cancelled, so
+    /// each thread may still finish reading one file.
+    ///
+    /// Defaults to `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::sync::{atomic::{AtomicBool, Ordering}, Arc};
+    /// use win32_version_info::VersionInfoBuilder;
+    ///
+    /// let cancel = Arc::new(AtomicBool::new(false));
+    /// let files = VersionInfoBuilder::new()
+    ///     .cancel_flag(Some(Arc::clone(&cancel)))
+    ///     .scan_dir_filtered(r"C:\Windows\System32", |_| true);
+    ///
+    /// for (count, (path, _)) in files.enumerate() {
+    ///     println!("{}", path.display());
+    ///     if count == 100 {
+    ///         // typically set from another thread
+    ///         cancel.store(true, Ordering::Relaxed);
+    ///     }
+    /// }
+    /// ```
+    pub fn cancel_flag(mut self, cancel_flag: Option<Arc<AtomicBool>>) -> Self {
+        self.cancel_flag = CancelFlag(cancel_flag);
+        self
+    }
+
+    /// The translations to read string fields from, in order of preference.
+    fn candidate_translations<'declared>(&self, declared: &'declared [Translation])
+    -> impl Iterator<Item = Translation> + 'declared {
+        const LANG_US_ENGLISH_CP_UNKNOWN: Translation = Translation::new(0x0409, 0x0000);
+        const LANG_US_ENGLISH_CP_UNICODE: Translation = Translation::new(0x0409, 0x04B0);
+        const LANG_US_ENGLISH_CP_USASCII: Translation = Translation::new(0x0409, 0x04E4);
+        const LANG_NEUTRAL_CP_UNICODE: Translation = Translation::new(0x0000, 0x04B0);
+        const LANG_NEUTRAL_CP_USASCII: Translation = Translation::new(0x0000, 0x04E4);
+        const FALLBACKS: [Translation; 5] = [
+            // anyway, these fallback values are exactly what .NET Framework uses =_=
+            // note that the code page in the translation id doesn't change
+            // how the strings are decoded, they are always UTF-16
+            LANG_US_ENGLISH_CP_UNICODE,
+            LANG_US_ENGLISH_CP_USASCII,
+            LANG_US_ENGLISH_CP_UNKNOWN,
+            // .NET stops here, but some files only have a string table
+            // in the neutral language without declaring it
+            LANG_NEUTRAL_CP_UNICODE,
+            LANG_NEUTRAL_CP_USASCII,
+        ];
+        let language = self.language;
+        self.translation
+            .into_iter()
+            .chain(declared
+                .iter()
+                .filter(move |translation| Some(translation.language_id) == language)
+                .copied())
+            .chain(declared
+                .iter()
+                .take(if self.merge_translations { usize::MAX } else { 1 })
+                .copied())
+            .chain(FALLBACKS
+                .into_iter()
+                .take(if self.disable_fallbacks { 0 } else { FALLBACKS.len() }))
+    }
+
+    /// Sets whether to read the string fields only from the translations
+    /// declared by the file, and from the one set with
+    /// [`VersionInfoBuilder::translation`], without falling back to US English
+    /// and the neutral language.
+    ///
+    /// This suits tools that must not misrepresent a file with string fields
+    /// of a translation it doesn't declare. The string fields are empty if
+    /// none of the translations tried has a non-empty `FileVersion`, e.g. if
+    /// the file declares no translation at all.
+    ///
+    /// Defaults to `false`.
+    pub const fn disable_fallbacks(mut self, disable_fallbacks: bool) -> Self {
+        self.disable_fallbacks = disable_fallbacks;
+        self
+    }
+
+    /// Sets whether to fill the `FileVersion` and `ProductVersion` string
+    /// fields from the fixed version information if they are empty, see
+    /// [`VersionInfoOs::merge_with_fixed`].
+    ///
+    /// This suits consumers reading only [`VersionInfo::file_version`] from
+    /// files that only set the numeric version.
+    ///
+    /// Defaults to `false`.
+    pub const fn fill_strings_from_fixed(mut self, fill_strings_from_fixed: bool) -> Self {
+        self.fill_strings_from_fixed = fill_strings_from_fixed;
+        self
+    }
+
+    /// Sets how ill-formed UTF-16 data in string values is converted by the
+    /// functions of this builder returning [`VersionInfo`].
+    ///
+    /// Defaults to [`InvalidUtf16Policy::Replace`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::{InvalidUtf16Policy, VersionInfoBuilder};
+    ///
+    /// let info = VersionInfoBuilder::new()
+    ///     .invalid_utf16_policy(InvalidUtf16Policy::Substitute('?'))
+    ///     .read("path/to/your/file.exe")
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// println!("File description: {}", info.file_description);
+    /// ```
+    pub const fn invalid_utf16_policy(mut self, invalid_utf16_policy: InvalidUtf16Policy) -> Self {
+        self.invalid_utf16_policy = invalid_utf16_policy;
+        self
+    }
+
+    /// Sets a language ID to prefer, e.g. `0x0407` for German.
+    ///
+    /// The translations declared by the file in this language are tried
+    /// before its other translations, in the order they are declared. If the
+    /// file has no translation in this language, the language is ignored.
+    ///
+    /// Defaults to `None`.
+    pub const fn language(mut self, language: Option<u16>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Sets whether to merge the string fields of all translations.
+    ///
+    /// By default, all string fields are read from a single translation: the
+    /// first one declared by the file whose `FileVersion` is non-empty, falling
+    /// back to US English and then to the neutral language, see
+    /// [`VersionInfoBuilder::disable_fallbacks`]. When merging, each field is
+    /// instead taken from the first translation in which it is non-empty,
+    /// trying every translation declared by the file in order before the
+    /// fallbacks.
+    ///
+    /// Note that this can produce version information whose fields come from
+    /// different languages, e.g. an English `CompanyName` alongside a German
+    /// `FileDescription`.
+    ///
+    /// Defaults to `false`.
+    pub const fn merge_translations(mut self, merge_translations: bool) -> Self {
+        self.merge_translations = merge_translations;
+        self
+    }
+
+    /// Sets whether to read the language-neutral version information of the
+    /// file, using `FILE_VER_GET_NEUTRAL` rather than
+    /// `FILE_VER_GET_LOCALISED`.
+    ///
+    /// By default, `GetFileVersionInfoExW` reads the localized version
+    /// information from the MUI resource file of the user's language if there
+    /// is one. With this option, it reads the version resource of the file
+    /// itself, see [`VersionInfoOs::from_file_dual`] to read
+    /// both at once.
+    ///
+    /// This option has no effect when bypassing the loader, which always reads
+    /// the version resource of the file itself.
+    ///
+    /// Defaults to `false`.
+    pub const fn neutral(mut self, neutral: bool) -> Self {
+        self.neutral = neutral;
+        self
+    }
+
+    /// Creates a builder with all options set to their defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of threads used by
+    /// [`VersionInfoBuilder::read_batch`].
+    ///
+    /// Defaults to `None`, which uses the available parallelism as reported
+    /// by [`std::thread::available_parallelism`]. `Some(0)` is treated as
+    /// `Some(1)`.
+    pub const fn parallelism(mut self, parallelism: Option<usize>) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Retrieves version information from the specified file with the options
+    /// of this builder.
+    ///
+    /// See [`VersionInfo::from_file`] for details.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    pub fn read<P: AsRef<Path>>(&self, file_name: P) -> Result<VersionInfo> {
+        self.read_os(file_name)
+            .map(|info| VersionInfo::from_os_with(&info, self.invalid_utf16_policy))
+    }
+
+    /// Retrieves version information from each of the specified files with
+    /// the options of this builder, returning the results keyed by the paths
+    /// as given.
+    ///
+    /// The files are read by a bounded pool of threads taking paths from a
+    /// shared queue, whose size is set by
+    /// [`VersionInfoBuilder::parallelism`], so the number of threads and open
+    /// handles stays bounded regardless of the number of files.
+    ///
+    /// If the [`VersionInfoBuilder::cancel_flag`] is set, the files not read
+    /// yet are left out of the results.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::VersionInfoBuilder;
+    ///
+    /// let infos = VersionInfoBuilder::new()
+    ///     .parallelism(Some(4))
+    ///     .read_batch(["path/to/your/file.exe", "path/to/your/file.dll"]);
+    ///
+    /// for (path, info) in infos {
+    ///     if let Ok(info) = info {
+    ///         println!("{}: {}", path.display(), info.file_version);
+    ///     }
+    /// }
+    /// ```
+    pub fn read_batch<I, P>(&self, file_names: I) -> HashMap<PathBuf, Result<VersionInfo>>
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf> {
+        let file_names = file_names.into_iter().map(Into::into).collect::<Vec<PathBuf>>();
+        let parallelism = self.parallelism
+            .or_else(|| thread::available_parallelism().ok().map(usize::from))
+            .unwrap_or(1)
+            .clamp(1, file_names.len().max(1));
+        let next = AtomicUsize::new(0);
+        let worker = || {
+            let mut results = Vec::new();
+            while let Some(file_name) = file_names
+                .get(next.fetch_add(1, Ordering::Relaxed))
+                .filter(|_| !self.cancel_flag.is_set()) {
+                results.push((file_name.clone(), self.read(file_name)));
+            }
+            results
+        };
+        thread::scope(|scope| {
+            // spawn all workers before joining any of them
+            let mut workers = Vec::with_capacity(parallelism);
+            for _ in 0..parallelism {
+                workers.push(scope.spawn(worker));
+            }
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().unwrap_or_else(|err| panic::resume_unwind(err)))
+                .collect()
+        })
+    }
+
+    /// Reads the version information block of the specified file, honoring
+    /// the options of this builder that apply to reading files.
+    fn read_block(&self, file_name: &Path) -> Result<VersionInfoBlock> {
+        const SHARING_VIOLATION_BACKOFF: Duration = Duration::from_millis(50);
+        const SHARING_VIOLATION_BACKOFF_MAX: Duration = Duration::from_secs(2);
+        let flags = if self.neutral {
+            sys::FILE_VER_GET_NEUTRAL
+        } else {
+            sys::FILE_VER_GET_LOCALISED
+        };
+        let read = |file_name: &Path| {
+            #[cfg(feature = "pe-parser")]
+            if self.bypass_loader {
+                return VersionInfoBlock::read_pe_file(file_name);
+            }
+            VersionInfoBlock::from_file_ex(file_name, flags)
+        };
+        let mut retries = 0;
+        loop {
+            // canonicalizing opens the file as well, so it is retried too
+            let ver_data = if self.resolve_symlinks {
+                fs::canonicalize(file_name)
+                    .map_err(|err| Error::new(ErrorKind::from_os(err)).with_path(file_name))
+                    .and_then(|file_name| read(&file_name))
+            } else {
+                read(file_name)
+            };
+            match ver_data {
+                Err(err) if matches!(err.kind(), ErrorKind::SharingViolation)
+                    && retries < self.sharing_violation_retries => {
+                    debug!("{} is used by another process, retrying", file_name.display());
+                    thread::sleep(SHARING_VIOLATION_BACKOFF
+                        .saturating_mul(2u32.saturating_pow(retries))
+                        .min(SHARING_VIOLATION_BACKOFF_MAX));
+                    retries += 1;
+                }
+                result => break result,
+            }
+        }
+    }
+
+    /// Retrieves both the language-neutral and the localized version
+    /// information of the specified file with the options of this builder, as
+    /// `(neutral, localized)`.
+    ///
+    /// See [`VersionInfoOs::from_file_dual`] for details. The
+    /// [`VersionInfoBuilder::neutral`] option is ignored.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    pub fn read_dual<P: AsRef<Path>>(&self, file_name: P) -> Result<(VersionInfo, VersionInfo)> {
+        let (neutral, localized) = self.read_dual_os(file_name)?;
+        Ok((
+            VersionInfo::from_os_with(&neutral, self.invalid_utf16_policy),
+            VersionInfo::from_os_with(&localized, self.invalid_utf16_policy)))
+    }
+
+    /// Retrieves both the language-neutral and the localized version
+    /// information of the specified file with the options of this builder,
+    /// preserving possibly ill-formed UTF-16 data.
+    ///
+    /// See [`VersionInfoOs::from_file_dual`] for details. The
+    /// [`VersionInfoBuilder::neutral`] option is ignored.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    pub fn read_dual_os<P: AsRef<Path>>(&self, file_name: P) -> Result<(VersionInfoOs, VersionInfoOs)> {
+        let file_name = file_name.as_ref();
+        let neutral = self.clone().neutral(true).read_os(file_name)?;
+        let localized = self.clone().neutral(false).read_os(file_name)?;
+        Ok((neutral, localized))
+    }
+
+    /// Reads the string fields of the specified file only if its fixed
+    /// version information satisfies `predicate`, see
+    /// [`VersionInfoBuilder::scan_dir_filtered`].
+    fn read_filtered<F>(&self, file_name: &Path, predicate: &mut F) -> Result<Option<VersionInfo>>
+    where
+        F: FnMut(&FixedFileInfo) -> bool {
+        let ver_data = match self.read_block(file_name) {
+            Ok(ver_data) => ver_data,
+            Err(err) if matches!(
+                err.kind(),
+                ErrorKind::NoVersionInfo
+                    | ErrorKind::UnsupportedFormat
+                    | ErrorKind::LegacyExecutableUnsupported) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        if !ver_data.get_fixed_file_info()?.is_some_and(|fixed_file_info| predicate(&fixed_file_info)) {
+            return Ok(None);
+        }
+        self.read_internal(&ver_data)
+            .map(|info| Some(VersionInfo::from_os_with(&info, self.invalid_utf16_policy)))
+    }
+
+    /// Retrieves version information from every file matching a glob pattern
+    /// with the options of this builder.
+    ///
+    /// Each item of the returned iterator pairs a matched path with the result
+    /// of reading it. Files without version information yield `Ok(None)`
+    /// rather than [`ErrorKind::NoVersionInfo`],
+    /// [`ErrorKind::UnsupportedFormat`] or
+    /// [`ErrorKind::LegacyExecutableUnsupported`], and directories are
+    /// skipped.
+    ///
+    /// The files are matched and read lazily as the iterator is advanced, so
+    /// patterns matching a huge number of files are processed without
+    /// collecting the matches first. The iterator ends early once the
+    /// [`VersionInfoBuilder::cancel_flag`] is set.
+    ///
+    /// This function requires the `glob` feature.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `pattern` is not a valid glob
+    /// pattern. Errors reading the matched files are yielded by the returned
+    /// iterator instead.
+    #[cfg(feature = "glob")]
+    pub fn read_glob(&self, pattern: &str) -> result::Result<
+        impl Iterator<Item = (PathBuf, Result<Option<VersionInfo>>)>,
+        glob::PatternError> {
+        let builder = self.clone();
+        let cancel_flag = self.cancel_flag.clone();
+        Ok(glob::glob(pattern)?
+            .take_while(move |_| !cancel_flag.is_set())
+            .filter(|entry| !entry.as_ref().is_ok_and(|path| path.is_dir()))
+            .map(move |entry| match entry {
+                Ok(path) => {
+                    let info = match builder.read(&path) {
+                        Ok(info) => Ok(Some(info)),
+                        Err(err) if matches!(
+                            err.kind(),
+                            ErrorKind::NoVersionInfo
+                                | ErrorKind::UnsupportedFormat
+                                | ErrorKind::LegacyExecutableUnsupported) => Ok(None),
+                        Err(err) => Err(err),
+                    };
+                    (path, info)
+                }
+                Err(err) => {
+                    let path = err.path().to_path_buf();
+                    let err = Error::new(ErrorKind::Os(err.into())).with_path(&path);
+                    (path, Err(err))
+                }
+            }))
+    }
+
+    fn read_internal(&self, ver_data: &VersionInfoBlock) -> Result<VersionInfoOs> {
+        let (mut ver_info, _) = self.read_selected(ver_data)?;
+        if self.fill_strings_from_fixed {
+            ver_info.merge_with_fixed();
+        }
+        Ok(ver_info)
+    }
+
+    /// Retrieves version information from the specified file with the options
+    /// of this builder, preserving possibly ill-formed UTF-16 data.
+    ///
+    /// See [`VersionInfoOs::from_file`] for details.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    pub fn read_os<P: AsRef<Path>>(&self, file_name: P) -> Result<VersionInfoOs> {
+        let file_name = file_name.as_ref();
+        self.read_internal(&self.read_block(file_name)?)
+            .map_err(|err| err.with_path(file_name))
+    }
+
+    /// Reads only the `ProductName` of the selected translation and the
+    /// numeric product version, see [`VersionInfo::product_only`].
+    fn read_product(&self, ver_data: &VersionInfoBlock) -> Result<(OsString, Option<FileVersion>)> {
+        let product_version = ver_data
+            .get_fixed_file_info()?
+            .map(|fixed_file_info| fixed_file_info.product_version);
+        let declared = ver_data.get_translations()?;
+        for translation in self.candidate_translations(&declared) {
+            // the translation is selected by its `FileVersion`, exactly as
+            // when reading all fields
+            if !ver_data.get_field_in_translation("FileVersion", translation, self)?.is_empty() {
+                let product_name = ver_data.get_field_in_translation("ProductName", translation, self)?;
+                return Ok((product_name, product_version));
+            }
+        }
+        Ok((OsString::new(), product_version))
+    }
+
+    /// Same as [`VersionInfoBuilder::read_internal`], also returning the
+    /// translation whose string table was selected, or `None` if the fields
+    /// were merged from several translations or none of them had any.
+    fn read_selected(
+        &self,
+        ver_data: &VersionInfoBlock) -> Result<(VersionInfoOs, Option<Translation>)> {
+        let mut ver_info = VersionInfoOs {
+            fixed_file_info: ver_data.get_fixed_file_info()?,
+            ..VersionInfoOs::default()
+        };
+        if let Some(translation) = self.translation_exact {
+            if ver_data.get_value_by_path(&format!("\\StringFileInfo\\{translation}"), 2)?.is_some() {
+                debug!("selected translation {translation} exactly");
+                ver_data.get_all_fields_in_translation(translation, self, &mut ver_info)?;
+                #[cfg(feature = "log")]
+                log_empty_fields(&ver_info);
+                return Ok((ver_info, Some(translation)));
+            }
+            if self.disable_fallbacks {
+                return Err(Error::new(ErrorKind::TranslationNotFound(translation)));
+            }
+            debug!("no string table for translation {translation}, falling back to the others");
+        }
+        let declared = ver_data.get_translations()?;
+        let translations = self.candidate_translations(&declared);
+        debug!("declared translations: {declared:?}");
+        for translation in translations {
+            let mut translated = ver_info.clone();
+            ver_data.get_all_fields_in_translation(translation, self, &mut translated)?;
+            if self.merge_translations {
+                debug!("merging the string table of translation {translation}");
+                ver_info.merge_empty_from(&translated);
+                continue;
+            }
+            if !translated.file_version.is_empty() {
+                debug!("selected translation {translation}");
+                #[cfg(feature = "log")]
+                log_empty_fields(&translated);
+                return Ok((translated, Some(translation)));
+            }
+            debug!("skipped translation {translation}, whose FileVersion is empty or missing");
+        }
+        if !self.merge_translations {
+            debug!("no translation has a FileVersion, so all string fields are empty");
+        }
+        #[cfg(feature = "log")]
+        log_empty_fields(&ver_info);
+        Ok((ver_info, None))
+    }
+
+    /// Sets whether to resolve symbolic links and junctions in the path with
+    /// [`std::fs::canonicalize`] before reading.
+    ///
+    /// `GetFileVersionInfoExW` already follows symbolic links to the file, so
+    /// this makes no difference to the version information read, except that
+    /// a broken link fails with the error of resolving it rather than that of
+    /// `GetFileVersionInfoExW`. It is mainly useful to make explicit that the
+    /// version information is that of the link target, as there is no way to
+    /// read version information of a link itself.
+    ///
+    /// Defaults to `false`.
+    pub const fn resolve_symlinks(mut self, resolve_symlinks: bool) -> Self {
+        self.resolve_symlinks = resolve_symlinks;
+        self
+    }
+
+    /// Walks a directory tree, yielding the version information of each file
+    /// whose fixed version information satisfies `predicate`, e.g. to find
+    /// all drivers among many files.
+    ///
+    /// The version information block of each file is read once, and only its
+    /// fixed version information is parsed before `predicate` is called. The
+    /// string fields are only read for the files matching `predicate`, which
+    /// avoids the lookups of string tables for the majority of files.
+    ///
+    /// The directory tree is walked depth-first and lazily as the iterator
+    /// is advanced, so huge trees are processed without collecting their
+    /// files first. Symbolic links and junctions to directories are not
+    /// followed, so the walk never loops.
+    ///
+    /// Files without version information or without fixed version
+    /// information, including those failing with [`ErrorKind::NoVersionInfo`],
+    /// [`ErrorKind::UnsupportedFormat`] or
+    /// [`ErrorKind::LegacyExecutableUnsupported`], are skipped, as they can't
+    /// match. Other errors reading a file or a directory are yielded with its
+    /// path.
+    ///
+    /// The iterator ends early once the [`VersionInfoBuilder::cancel_flag`] is
+    /// set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::VersionInfoBuilder;
+    ///
+    /// const VFT_DRV: u32 = 3;
+    ///
+    /// let drivers = VersionInfoBuilder::new().scan_dir_filtered(
+    ///     r"C:\Windows\System32",
+    ///     |fixed_file_info| fixed_file_info.file_type == VFT_DRV);
+    ///
+    /// for (path, info) in drivers {
+    ///     if let Ok(info) = info {
+    ///         println!("{}: {}", path.display(), info.file_description);
+    ///     }
+    /// }
+    /// ```
+    pub fn scan_dir_filtered<P, F>(&self, dir: P, mut predicate: F)
+    -> impl Iterator<Item = (PathBuf, Result<VersionInfo>)>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&FixedFileInfo) -> bool {
+        let builder = self.clone();
+        let mut pending = vec![dir.as_ref().to_path_buf()];
+        let mut current: Option<(PathBuf, fs::ReadDir)> = None;
+        iter::from_fn(move || loop {
+            if builder.cancel_flag.is_set() {
+                return None;
+            }
+            if current.is_none() {
+                let dir = pending.pop()?;
+                match fs::read_dir(&dir) {
+                    Ok(entries) => current = Some((dir, entries)),
+                    Err(err) => {
+                        let err = Error::new(ErrorKind::Os(err)).with_path(&dir);
+                        return Some((dir, Err(err)));
+                    }
+                }
+            }
+            let Some((dir, entries)) = current.as_mut() else {
+                continue;
+            };
+            let entry = match entries.next() {
+                Some(Ok(entry)) => entry,
+                Some(Err(err)) => {
+                    let err = Error::new(ErrorKind::Os(err)).with_path(dir);
+                    return Some((dir.clone(), Err(err)));
+                }
+                None => {
+                    current = None;
+                    continue;
+                }
+            };
+            let path = entry.path();
+            // `DirEntry::file_type` doesn't follow links, unlike `Path::is_dir`
+            if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                pending.push(path);
+                continue;
+            }
+            if path.is_dir() {
+                continue;
+            }
+            match builder.read_filtered(&path, &mut predicate) {
+                Ok(Some(info)) => return Some((path, Ok(info))),
+                Ok(None) => (),
+                Err(err) => return Some((path, Err(err))),
+            }
+        })
+    }
+
+    /// Sets how many times to retry reading a file that fails with
+    /// [`ErrorKind::SharingViolation`], e.g. because an antivirus holds it
+    /// open exclusively.
+    ///
+    /// The first retry waits for 50 milliseconds, and each further retry waits
+    /// twice as long as the previous one, but at most 2 seconds. If the last
+    /// retry still fails, its error is returned.
+    ///
+    /// Defaults to `0`, i.e. no retries.
+    pub const fn sharing_violation_retries(mut self, sharing_violation_retries: u32) -> Self {
+        self.sharing_violation_retries = sharing_violation_retries;
+        self
+    }
+
+    /// Sets how trailing NUL characters are removed from string values.
+    ///
+    /// Defaults to [`TrailingNul::StripAll`].
+    pub const fn trailing_nul(mut self, trailing_nul: TrailingNul) -> Self {
+        self.trailing_nul = trailing_nul;
+        self
+    }
+
+    /// Sets a translation to read the string fields from, e.g. one parsed
+    /// from `"040904b0"`.
+    ///
+    /// The translation is tried before the ones declared by the file, which
+    /// are still tried if the file has no string table for it, or its
+    /// `FileVersion` is empty.
+    ///
+    /// Defaults to `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::VersionInfoBuilder;
+    ///
+    /// let info = VersionInfoBuilder::new()
+    ///     .translation(Some("080404b0".parse().unwrap()))
+    ///     .read("path/to/your/file.exe")
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// println!("File description: {}", info.file_description);
+    /// ```
+    pub const fn translation(mut self, translation: Option<Translation>) -> Self {
+        self.translation = translation;
+        self
+    }
+
+    /// Sets a translation to read exactly the string fields of, e.g. to read
+    /// the Unicode string table of a file rather than an ANSI one in the same
+    /// language.
+    ///
+    /// The language ID and the code page must both match a string table of
+    /// the file, whether or not the file declares the translation. Unlike
+    /// [`VersionInfoBuilder::translation`], no other translation is tried if
+    /// the string table exists, even if its `FileVersion` is empty. If it
+    /// doesn't exist, reading fails with [`ErrorKind::TranslationNotFound`]
+    /// if [`VersionInfoBuilder::disable_fallbacks`] is set, and the string
+    /// fields are selected as without this option otherwise.
+    ///
+    /// Defaults to `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::{Translation, VersionInfoBuilder};
+    ///
+    /// // US English in Windows-1252 rather than in UTF-16, if there is one
+    /// let info = VersionInfoBuilder::new()
+    ///     .translation_exact(Some(Translation::new(0x0409, 0x04E4)))
+    ///     .read("path/to/your/file.exe")
+    ///     .expect("Failed to retrieve version information");
+    ///
+    /// println!("File description: {}", info.file_description);
+    /// ```
+    pub const fn translation_exact(mut self, translation_exact: Option<Translation>) -> Self {
+        self.translation_exact = translation_exact;
+        self
+    }
+
+    /// Sets whether to trim leading and trailing whitespace from string
+    /// values, e.g. the padding of `FileVersion` in some files.
+    ///
+    /// Whitespace is trimmed after trailing NULs are removed as set by
+    /// [`VersionInfoBuilder::trailing_nul`], so it is not trimmed before kept
+    /// NULs.
+    ///
+    /// Defaults to `false`.
+    pub const fn trim_whitespace(mut self, trim_whitespace: bool) -> Self {
+        self.trim_whitespace = trim_whitespace;
+        self
+    }
+}
+
+/// The error type for retrieving version information.
+///
+/// The cause of the error is given by [`Error::kind`]. Errors reading a file
+/// also carry its path, which is included when the error is displayed, e.g.
+/// `failed to read version info from "foo.dll": The system cannot find the
+/// file specified. (os error 2)`.
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+    path: Option<PathBuf>,
+}
+
+/// The cause of an [`Error`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The data is not a valid PE image.
+    InvalidPeImage,
+    /// The file is a legacy 16-bit (NE format) executable, whose version
+    /// information is not supported.
+    LegacyExecutableUnsupported,
+    /// The version information block is malformed, e.g. a node in it claims
+    /// to be longer than its parent.
+    MalformedBlock,
+    /// The file has no version information.
+    NoVersionInfo,
+    /// An error reported by the operating system.
+    Os(io::Error),
+    /// The path is longer than Windows supports, i.e. 32767 UTF-16 code
+    /// units even with the `\\?\` prefix, or longer than `MAX_PATH` without
+    /// the prefix where long paths are not enabled.
+    PathTooLong,
+    /// Access to the process with the given ID was denied, which is usually
+    /// the case for protected processes.
+    ProcessAccessDenied(u32),
+    /// The file is open by another process that denies sharing it, e.g. an
+    /// antivirus scanning it. Reading may succeed when retried later, see
+    /// [`VersionInfoBuilder::sharing_violation_retries`].
+    SharingViolation,
+    /// The file has no string table for the given translation, see
+    /// [`VersionInfoOs::from_file_with_translation`].
+    TranslationNotFound(Translation),
+    /// The file is in a format that cannot have a version resource, e.g. a
+    /// Windows Installer package (`.msi`, `.msp`).
+    ///
+    /// Windows Installer packages are OLE compound files, which store their
+    /// version in the `ProductVersion` property of the `Property` table of
+    /// the installer database, not in a version resource nor in their
+    /// `SummaryInformation` stream. They can be read with the Windows
+    /// Installer API, e.g. `MsiOpenDatabaseW`, which this crate doesn't use.
+    UnsupportedFormat,
+}
+
+/// A specialized [`Result`](std::result::Result) type for retrieving version
+/// information.
+pub type Result<T> = result::Result<T, Error>;
+
+impl Error {
+    /// Consumes the error, returning its cause.
+    #[must_use]
+    pub fn into_kind(self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the cause of the error.
+    #[must_use]
+    pub const fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    const fn new(kind: ErrorKind) -> Self {
+        Self { kind, path: None }
+    }
+
+    /// Returns the path of the file being read when the error occurred, if
+    /// any.
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    fn with_path(self, path: &Path) -> Self {
+        Self {
+            path: Some(path.to_owned()),
+            ..self
+        }
+    }
+}
+
+impl ErrorKind {
+    /// Maps an OS error to its dedicated kind, if any, or [`ErrorKind::Os`].
+    fn from_os(err: io::Error) -> Self {
+        match err.raw_os_error() {
+            Some(sys::ERROR_SHARING_VIOLATION) => Self::SharingViolation,
+            Some(sys::ERROR_FILENAME_EXCED_RANGE) => Self::PathTooLong,
+            _ => Self::Os(err),
+        }
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Self {
+        Self::new(kind)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(path) = &self.path {
+            write!(f, "failed to read version info from \"{}\": {}", path.display(), self.kind)
+        } else {
+            self.kind.fmt(f)
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        // the message of an OS error is already part of the displayed error
+        if let ErrorKind::Os(err) = &self.kind {
+            err.source()
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoVersionInfo =>
+                f.write_str("the file has no version information"),
+            Self::LegacyExecutableUnsupported =>
+                f.write_str("the file is a legacy 16-bit executable, whose version information is not supported"),
+            Self::UnsupportedFormat =>
+                f.write_str("the file is in a format without version information, e.g. a Windows Installer package"),
+            Self::ProcessAccessDenied(pid) =>
+                write!(f, "access denied to process {pid}, which may be a protected process"),
+            Self::MalformedBlock =>
+                f.write_str("the version information block is malformed"),
+            Self::InvalidPeImage =>
+                f.write_str("the data is not a valid PE image"),
+            Self::TranslationNotFound(translation) =>
+                write!(f, "the file has no string table for translation {translation}"),
+            Self::SharingViolation =>
+                f.write_str("the file is being used by another process"),
+            Self::PathTooLong =>
+                f.write_str("the path is too long"),
+            Self::Os(err) => err.fmt(f),
+        }
+    }
+}
+
+/// Represents a raw version information block, i.e. the data returned by
+/// `GetFileVersionInfoW` or the content of an `RT_VERSION` resource.
+///
+/// This gives access to any value in the block with
+/// [`VersionInfoBlock::query_raw`], for needs not covered by [`VersionInfo`]
+/// and [`VersionInfoOs`].
+///
+/// [`VersionInfo`] and [`VersionInfoOs`] own copies of their fields and never
+/// borrow from the block, so after reading the fields of a block with
+/// [`VersionInfoOs::from_block`], dropping the block frees its buffer while
+/// the fields can be kept for as long as needed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use win32_version_info::VersionInfoBlock;
+///
+/// let block = VersionInfoBlock::from_file("path/to/your/file.exe")
+///     .expect("Failed to retrieve version information");
+///
+/// let translations = block.query_raw("\\VarFileInfo\\Translation")
+///     .expect("Malformed version information");
+/// println!("Translations: {translations:02x?}");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VersionInfoBlock(Vec<u8>);
+
+impl VersionInfoBlock {
+    /// Returns the bytes of the block.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Describes the structure of the block as a human-readable tree, e.g.
+    /// for bug reports, see [`VersionInfoOs::dump_structure`].
+    #[must_use]
+    pub fn dump_structure(&self) -> String {
+        block::dump(&self.0)
+    }
+
+    /// Copies a raw version information block from `data`.
+    ///
+    /// The block is validated first, so malformed data results in an error
+    /// rather than out-of-bounds reads when querying it.
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`ErrorKind::MalformedBlock`] if `data` is not a
+    /// well-formed version information block.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        if block::validate(data) {
+            Ok(Self(data.to_vec()))
+        } else {
+            Err(Error::new(ErrorKind::MalformedBlock))
+        }
+    }
+
+    /// Copies the raw version information block of the module containing
+    /// this crate, i.e. the DLL it is linked into, or the executable if it is
+    /// linked into one.
+    ///
+    /// The module is found with `GetModuleHandleExW` from an address within
+    /// this crate, and then read as in [`VersionInfoBlock::from_module`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The module has no version information, see [`ErrorKind::NoVersionInfo`].
+    /// - The version resource is malformed, see [`ErrorKind::MalformedBlock`].
+    pub fn from_current_module() -> Result<Self> {
+        let module = sys::current_module().map_err(|err| Error::new(ErrorKind::from_os(err)))?;
+        // the module contains this code, so it stays loaded during the call
+        unsafe { Self::from_module(module) }
+    }
+
+    /// Copies the raw version information block of the executable of the
+    /// current process, as in [`VersionInfoBlock::from_module`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The executable has no version information, see
+    ///   [`ErrorKind::NoVersionInfo`].
+    /// - The version resource is malformed, see [`ErrorKind::MalformedBlock`].
+    pub fn from_executable_module() -> Result<Self> {
+        // a null module stands for the executable, which is always loaded
+        unsafe { Self::from_module(ptr::null_mut()) }
+    }
+
+    /// Retrieves the raw version information block of the specified file.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    /// - The file is a legacy 16-bit executable, see
+    ///   [`ErrorKind::LegacyExecutableUnsupported`].
+    /// - The file is a Windows Installer package, see
+    ///   [`ErrorKind::UnsupportedFormat`].
+    /// - The path is too long, see [`ErrorKind::PathTooLong`].
+    ///
+    /// # Case-sensitive directories
+    ///
+    /// In directories with case sensitivity enabled, e.g. those shared with
+    /// WSL, `GetFileVersionInfoExW` only finds the file if the case of the path
+    /// matches the file on disk. If the file is not found, the path is
+    /// canonicalized with [`std::fs::canonicalize`] and, if that resolves it
+    /// to a different path, reading is retried once with it. Otherwise the
+    /// original error is returned.
+    ///
+    /// # Forward slashes
+    ///
+    /// Forward slashes in the path are replaced with backslashes before
+    /// calling `GetFileVersionInfoExW`, which fails on some paths with forward
+    /// slashes, e.g. `C:/Windows/System32/kernel32.dll`. Verbatim paths
+    /// starting with `\\?\` are passed as is, as Windows doesn't treat forward
+    /// slashes in them as separators.
+    ///
+    /// # Windows versions
+    ///
+    /// Blocks are always read with the `Ex` variants, i.e.
+    /// `GetFileVersionInfoSizeExW` and `GetFileVersionInfoExW`, or their ANSI
+    /// counterparts for [`VersionInfoOs::from_file_ansi`], with explicit
+    /// `FILE_VER_GET_*` flags. On some versions of Windows, e.g. Windows 7,
+    /// `GetFileVersionInfoW` doesn't resolve the strings of MUI files that the
+    /// `Ex` variants resolve. The `Ex` variants are available since Windows
+    /// Vista, i.e. on every version of Windows supported by Rust, so there is
+    /// no fallback to the non-`Ex` functions.
+    pub fn from_file<P: AsRef<Path>>(file_name: P) -> Result<Self> {
+        Self::from_file_ex(file_name, sys::FILE_VER_GET_LOCALISED)
+    }
+
+    fn from_file_ex<P: AsRef<Path>>(
+        file_name: P,
+        flags: u32) -> Result<Self> {
+        Self::retry_canonicalized(file_name.as_ref(), |file_name| Self::read_file_ex(file_name, flags))
+    }
+
+    /// Copies the raw version information block of a module loaded in the
+    /// current process, or of the executable of the current process if
+    /// `module` is null, e.g. the `HMODULE` passed to `DllMain`.
+    ///
+    /// The `RT_VERSION` resource is read from the module in memory with
+    /// `FindResourceW` and `LoadResource`, without resolving its path or
+    /// opening its file, and then validated as in
+    /// [`VersionInfoBlock::from_bytes`]. Like
+    /// [`VersionInfoBuilder::bypass_loader`], this reads the resource of the
+    /// module itself, without the localized resources of its MUI file.
+    ///
+    /// See also [`VersionInfoBlock::from_current_module`] and
+    /// [`VersionInfoBlock::from_executable_module`], which need no handle.
+    ///
+    /// # Safety
+    ///
+    /// `module` must be null or the `HMODULE` of a module loaded in the
+    /// current process, which must stay loaded during the call.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The module has no version information, see [`ErrorKind::NoVersionInfo`].
+    /// - The version resource is malformed, see [`ErrorKind::MalformedBlock`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::ffi::c_void;
+    /// use win32_version_info::{VersionInfoBlock, VersionInfoOs};
+    ///
+    /// /// Reads the version of a module loaded by the host, e.g. a sibling DLL.
+    /// fn module_version(module: *mut c_void) -> Option<String> {
+    ///     // SAFETY: the host keeps the module loaded while calling into us
+    ///     let block = unsafe { VersionInfoBlock::from_module(module) }.ok()?;
+    ///     let info = VersionInfoOs::from_block(&block).ok()?;
+    ///     Some(info.file_version.to_string_lossy().into_owned())
+    /// }
+    /// ```
+    pub unsafe fn from_module(module: *mut ffi::c_void) -> Result<Self> {
+        let data = unsafe { sys::module_version_resource(module) }.map_err(|err| {
+            let kind = if [
+                sys::ERROR_RESOURCE_DATA_NOT_FOUND,
+                sys::ERROR_RESOURCE_NAME_NOT_FOUND,
+                sys::ERROR_RESOURCE_TYPE_NOT_FOUND,
+            ].contains(&err.raw_os_error().unwrap_or_default()) {
+                ErrorKind::NoVersionInfo
+            } else {
+                ErrorKind::from_os(err)
+            };
+            Error::new(kind)
+        })?;
+        Self::from_bytes(&data)
+    }
+
+    fn get_all_fields_in_translation(
+        &self,
+        translation: Translation,
+        options: &VersionInfoBuilder,
+        info: &mut VersionInfoOs) -> Result<()> {
+        for (name, field) in STANDARD_FIELDS.iter().zip(info.fields_mut()) {
+            *field = self.get_field_in_translation(name, translation, options)?;
+        }
+        Ok(())
+    }
+
+    fn get_field_in_translation(
+        &self,
+        name: &str,
+        translation: Translation,
+        options: &VersionInfoBuilder) -> Result<OsString> {
+        let path = format!("\\StringFileInfo\\{translation}\\{name}");
+        Ok(self.get_value_by_path(&path, 2)?
+            .map(|value| {
+                let mut units = value
+                    .chunks_exact(2)
+                    .filter_map(|unit| unit.try_into().ok().map(u16::from_le_bytes))
+                    .collect::<Vec<_>>();
+                match options.trailing_nul {
+                    TrailingNul::StripAll => while units.last() == Some(&0) {
+                        units.pop();
+                    },
+                    TrailingNul::StripOne => if units.last() == Some(&0) {
+                        units.pop();
+                    },
+                    TrailingNul::Keep => (),
+                }
+                if options.trim_whitespace {
+                    let is_whitespace = |unit: &u16| char::from_u32(u32::from(*unit))
+                        .is_some_and(char::is_whitespace);
+                    let end = units.iter().rposition(|unit| !is_whitespace(unit)).map_or(0, |last| last + 1);
+                    units.truncate(end);
+                    let start = units.iter().position(|unit| !is_whitespace(unit)).unwrap_or(units.len());
+                    units.drain(..start);
+                }
+                OsString::from_wide(&units)
+            })
+            .unwrap_or_default())
+    }
+
+    fn get_fixed_file_info(&self) -> Result<Option<FixedFileInfo>> {
+        Ok(self.get_raw_fixed_file_info()?.map(FixedFileInfo::from))
+    }
+
+    fn get_raw_fixed_file_info(&self) -> Result<Option<VsFixedFileInfo>> {
+        block::root_value(&self.0)
+            .map(VsFixedFileInfo::parse)
+            .ok_or(Error::new(ErrorKind::MalformedBlock))
+    }
+
+    fn get_translations(&self) -> Result<Vec<Translation>> {
+        Ok(self.get_value_by_path("\\VarFileInfo\\Translation", 1)?
+            .map(|value| value
+                .chunks_exact(4)
+                .filter_map(|raw| raw.try_into().ok().map(Translation::from_le_bytes))
+                .collect())
+            .unwrap_or_default())
+    }
+
+    /// Queries the value at `path`, whose length `VerQueryValueW` reports in
+    /// units of `unit_size` bytes, i.e. 2 for text values and 1 for binary
+    /// ones.
+    ///
+    /// Returns `Ok(None)` if there is no such value, and fails with
+    /// [`ErrorKind::MalformedBlock`] if the value doesn't lie within the block.
+    fn get_value_by_path(&self, path: &str, unit_size: usize) -> Result<Option<&[u8]>> {
+        let path = path
+            .encode_utf16()
+            .chain(Some(0))
+            .collect::<Vec<_>>();
+        let Some((ptr, len)) = (unsafe { sys::ver_query_value(&self.0, &path) }) else {
+            return Ok(None);
+        };
+        // an empty value may point anywhere, including outside the block
+        if len == 0 {
+            return Ok(Some(&[]));
+        }
+        // `VerQueryValueW` walks the block by the lengths stored in it, so
+        // neither the offset nor the length of the value can be trusted
+        ptr.addr()
+            .checked_sub(self.0.as_ptr().addr())
+            .zip((len as usize).checked_mul(unit_size))
+            .and_then(|(offset, size)| self.0.get(offset..offset.checked_add(size)?))
+            .map(Some)
+            .ok_or(Error::new(ErrorKind::MalformedBlock))
+    }
+
+    /// Consumes the block, returning its bytes, e.g. to reuse their
+    /// allocation with [`VersionInfoBlock::read_into`].
+    #[must_use]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Queries the raw bytes of the value at `path`, with the same syntax as
+    /// `VerQueryValueW`, e.g.:
+    ///
+    /// - `\` for the `VS_FIXEDFILEINFO`, see also [`VsFixedFileInfo`].
+    /// - `\VarFileInfo\Translation` for the array of translations.
+    /// - `\StringFileInfo\040904b0\FileVersion` for a string value, which
+    ///   is returned as little-endian UTF-16 including any trailing NULs.
+    ///
+    /// Returns `Ok(None)` if there is no value at `path`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`ErrorKind::MalformedBlock`] if the value
+    /// doesn't lie within the block.
+    pub fn query_raw(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        // `VerQueryValueW` reports the length of string values in characters
+        // and of anything else in bytes
+        let mut components = path.split('\\').filter(|component| !component.is_empty());
+        let unit_size = if components
+            .next()
+            .is_some_and(|component| component.eq_ignore_ascii_case("StringFileInfo"))
+            && components.count() == 2 { 2 } else { 1 };
+        Ok(self.get_value_by_path(path, unit_size)?.map(<[u8]>::to_vec))
+    }
+
+    /// Reads the block with `GetFileVersionInfoExA`, for a path encoded in
+    /// the ANSI code page.
+    fn read_file_ansi(file_name: &ffi::CStr) -> Result<Self> {
+        // a character takes at least as many bytes as UTF-16 code units, so
+        // this never lets a path longer than `MAX_PATH_LEN` through
+        if file_name.to_bytes().len() > MAX_PATH_LEN {
+            return Err(Error::new(ErrorKind::PathTooLong).with_path(&ansi_path(file_name)));
+        }
+        let size = sys::get_file_version_info_size_ansi(sys::FILE_VER_GET_LOCALISED, file_name)
+            .map_err(|err| Self::size_query_error(err, &ansi_path(file_name)))?;
+        sys::get_file_version_info_ansi(sys::FILE_VER_GET_LOCALISED, file_name, size)
+            .map(Self)
+            .map_err(|err| Error::new(ErrorKind::from_os(err)).with_path(&ansi_path(file_name)))
+    }
+
+    /// Reads the block with `GetFileVersionInfoExW`, querying its size with
+    /// `GetFileVersionInfoSizeExW` and the same `flags`, as the size of the
+    /// localized and the neutral block may differ.
+    fn read_file_ex(file_name: &Path, flags: u32) -> Result<Self> {
+        let mut data = Vec::new();
+        Self::read_file_ex_into(file_name, flags, &mut data)?;
+        Ok(Self(data))
+    }
+
+    /// Same as [`VersionInfoBlock::read_file_ex`], reading into `data`.
+    fn read_file_ex_into(file_name: &Path, flags: u32, data: &mut Vec<u8>) -> Result<()> {
+        const SLASH: u16 = b'/' as u16;
+        const BACKSLASH: u16 = b'\\' as u16;
+        let verbatim = file_name.as_os_str().encode_wide().take(4).eq(r"\\?\".encode_utf16());
+        let mut wide_file_name = file_name
+            .as_os_str()
+            .encode_wide()
+            .map(|unit| if unit == SLASH && !verbatim { BACKSLASH } else { unit })
+            .collect::<Vec<_>>();
+        if wide_file_name.len() > MAX_PATH_LEN {
+            return Err(Error::new(ErrorKind::PathTooLong).with_path(file_name));
+        }
+        wide_file_name.push(0);
+        let size = sys::get_file_version_info_size(flags, &wide_file_name)
+            .map_err(|err| Self::size_query_error(err, file_name))?;
+        sys::get_file_version_info(flags, &wide_file_name, size, data)
+            .map_err(|err| Error::new(ErrorKind::from_os(err)).with_path(file_name))
+    }
+
+    /// Retrieves the raw version information block of the specified file into
+    /// the allocation of `buffer`, which is *moved* into the returned block
+    /// and must be given back with `buffer = block.into_bytes()` to be reused
+    /// for the next file. Otherwise, every call allocates anew, exactly as
+    /// [`VersionInfoBlock::from_file`] does.
+    ///
+    /// On success, `buffer` is left empty. The allocation is only grown if the
+    /// block of the file doesn't fit. On failure, `buffer` keeps its
+    /// allocation.
+    ///
+    /// The block is otherwise read exactly as in
+    /// [`VersionInfoBlock::from_file`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    /// - The file has no version information, see [`ErrorKind::NoVersionInfo`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use win32_version_info::{VersionInfoBlock, VersionInfoOs};
+    ///
+    /// let mut buffer = Vec::new();
+    /// for file_name in ["path/to/your/file.exe", "path/to/your/file.dll"] {
+    ///     let Ok(block) = VersionInfoBlock::read_into(file_name, &mut buffer) else {
+    ///         continue;
+    ///     };
+    ///     if let Ok(info) = VersionInfoOs::from_block(&block) {
+    ///         println!("File version: {}", info.file_version.to_string_lossy());
+    ///     }
+    ///     buffer = block.into_bytes();
+    /// }
+    /// ```
+    pub fn read_into<P: AsRef<Path>>(file_name: P, buffer: &mut Vec<u8>) -> Result<Self> {
+        let mut data = mem::take(buffer);
+        let result = Self::retry_canonicalized(file_name.as_ref(), |file_name| {
+            Self::read_file_ex_into(file_name, sys::FILE_VER_GET_LOCALISED, &mut data)
+        });
+        match result {
+            Ok(()) => Ok(Self(data)),
+            Err(err) => {
+                *buffer = data;
+                Err(err)
+            }
+        }
+    }
+
+    /// Reads the `RT_VERSION` resource of a PE file directly, bypassing the
+    /// loader, see [`VersionInfoBuilder::bypass_loader`].
+    #[cfg(feature = "pe-parser")]
+    fn read_pe_file(file_name: &Path) -> Result<Self> {
+        File::open(file_name)
+            .map_err(|err| Error::new(ErrorKind::from_os(err)))
+            .and_then(|mut file| pe::read_version_resource(&mut file))
+            .and_then(|data| data.ok_or(Error::new(ErrorKind::NoVersionInfo)))
+            .and_then(|data| Self::from_bytes(&data))
+            .map_err(|err| err.with_path(file_name))
+    }
+
+    /// Reads the file with `read`, retrying once with the canonicalized path
+    /// if the file is not found, see [`VersionInfoBlock::from_file`].
+    fn retry_canonicalized<T>(file_name: &Path, mut read: impl FnMut(&Path) -> Result<T>) -> Result<T> {
+        read(file_name).or_else(|err| {
+            if !matches!(err.kind(), ErrorKind::Os(err) if err.kind() == io::ErrorKind::NotFound) {
+                return Err(err);
+            }
+            match fs::canonicalize(file_name) {
+                Ok(canonical) if canonical != file_name => {
+                    debug!("{} not found, retrying with {}", file_name.display(), canonical.display());
+                    read(&canonical)
+                }
+                _ => Err(err),
+            }
+        })
+    }
+
+    /// Maps the error of a failed `GetFileVersionInfoSize*` call.
+    fn size_query_error(err: io::Error, file_name: &Path) -> Error {
+        let kind = if is_legacy_executable(file_name).unwrap_or(false) {
+            ErrorKind::LegacyExecutableUnsupported
+        } else if is_compound_file(file_name).unwrap_or(false) {
+            ErrorKind::UnsupportedFormat
+        } else if [
+            sys::ERROR_RESOURCE_DATA_NOT_FOUND,
+            sys::ERROR_RESOURCE_NAME_NOT_FOUND,
+            sys::ERROR_RESOURCE_TYPE_NOT_FOUND,
+        ].contains(&err.raw_os_error().unwrap_or_default()) {
+            ErrorKind::NoVersionInfo
+        } else {
+            ErrorKind::from_os(err)
+        };
+        Error::new(kind).with_path(file_name)
+    }
+
+    /// Returns the translations declared in the `\VarFileInfo\Translation`
+    /// value of the block, in the order they are declared.
+    ///
+    /// Returns an empty [`Vec`] if the block declares no translations, either
+    /// because the value is missing or because it is empty.
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`ErrorKind::MalformedBlock`] if the value
+    /// doesn't lie within the block.
+    pub fn translations(&self) -> Result<Vec<Translation>> {
+        self.get_translations()
+    }
+}
+
+fn process_image_path(pid: u32) -> Result<OsString> {
+    sys::process_image_name(pid).map_err(|err| {
+        if err.raw_os_error() == Some(sys::ERROR_ACCESS_DENIED) {
+            Error::new(ErrorKind::ProcessAccessDenied(pid))
+        } else {
+            Error::new(ErrorKind::from_os(err))
+        }
+    })
+}
+
+/// Retrieves the target architecture of the specified PE file, e.g. whether a
+/// DLL is built for x86, x64 or ARM64.
+///
+/// The architecture is not part of the version information but of the PE
+/// headers, which are parsed directly without reading the rest of the file.
+/// This function requires the `pe-parser` feature.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The file does not exist.
+/// - The file is not accessible.
+/// - The file is not a valid PE image, see [`ErrorKind::InvalidPeImage`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use win32_version_info::{pe_machine, Machine};
+///
+/// let machine = pe_machine("path/to/your/file.dll")
+///     .expect("Failed to read the PE headers");
+///
+/// assert_eq!(machine, Machine::X64);
+/// ```
+#[cfg(feature = "pe-parser")]
+pub fn pe_machine<P: AsRef<Path>>(file_name: P) -> Result<Machine> {
+    let file_name = file_name.as_ref();
+    File::open(file_name)
+        .map_err(|err| Error::new(ErrorKind::from_os(err)))
+        .and_then(|mut file| pe::read_machine(&mut file))
+        .map_err(|err| err.with_path(file_name))
+}
+
+/// Resolves `file_name` relative to the directory `dir`, failing if it could
+/// lead outside the directory.
+fn path_at(dir: BorrowedHandle<'_>, file_name: &Path) -> Result<PathBuf> {
+    let mut path = sys::final_path_name(dir)
+        .map(PathBuf::from)
+        .map_err(|err| Error::new(ErrorKind::Os(err)).with_path(file_name))?;
+    // `\\?\` paths are not normalized, so only push plain file names, which
+    // also joins them with backslashes
+    for component in file_name.components() {
+        match component {
+            Component::Normal(name) => path.push(name),
+            Component::CurDir => (),
+            Component::Prefix(_) | Component::RootDir | Component::ParentDir => return Err(Error::new(ErrorKind::Os(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the path must be relative and must not have `..` components")))
+                .with_path(file_name)),
+        }
+    }
+    Ok(path)
+}
+
+/// Decodes a path encoded in the ANSI code page, e.g. to report it in errors.
+fn ansi_path(file_name: &ffi::CStr) -> PathBuf {
+    PathBuf::from(OsString::from_wide(&sys::ansi_to_wide(file_name.to_bytes())))
+}
+
+/// Logs the string fields left empty after selecting a translation, e.g. to
+/// diagnose why a field expected to be set is not.
+#[cfg(feature = "log")]
+fn log_empty_fields(info: &VersionInfoOs) {
+    for (name, value) in STANDARD_FIELDS.iter().zip(info.field_values()) {
+        if value.is_empty() {
+            log::debug!("field {name} is empty or missing");
+        }
+    }
+}
+
+/// Checks whether the file is a legacy 16-bit executable, i.e. an `MZ` header
+/// pointing to an `NE` header rather than a `PE` one.
+fn is_legacy_executable(file_name: &Path) -> io::Result<bool> {
+    let mut file = File::open(file_name)?;
+    let mut dos_header = [0u8; 0x40];
+    file.read_exact(&mut dos_header)?;
+    if &dos_header[..2] != b"MZ" {
+        return Ok(false);
+    }
+    let mut e_lfanew = [0u8; 4];
+    e_lfanew.copy_from_slice(&dos_header[0x3C..]);
+    file.seek(SeekFrom::Start(u32::from_le_bytes(e_lfanew).into()))?;
+    let mut signature = [0u8; 2];
+    file.read_exact(&mut signature)?;
+    Ok(&signature == b"NE")
+}
+
+/// Checks whether the file is an OLE compound file, e.g. a Windows Installer
+/// package, by its signature.
+fn is_compound_file(file_name: &Path) -> io::Result<bool> {
+    const SIGNATURE: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+    let mut signature = [0u8; 8];
+    File::open(file_name)?.read_exact(&mut signature)?;
+    Ok(signature == SIGNATURE)
 }
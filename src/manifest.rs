@@ -0,0 +1,151 @@
+//! Reads the version of the assembly identity in the application manifest
+//! embedded in a PE file.
+//!
+//! Manifests are XML documents, of which only the start tags of elements are
+//! of interest here, so they are scanned rather than fully parsed.
+
+use std::{
+    fs::File,
+    path::Path,
+};
+
+use crate::{
+    pe,
+    Error,
+    ErrorKind,
+    Result,
+};
+
+/// Retrieves the version of the application manifest embedded in the
+/// specified PE file, i.e. the `version` attribute of its `assemblyIdentity`
+/// element, e.g. `1.0.0.0`.
+///
+/// The manifest is the first `RT_MANIFEST` resource of the file, which is read
+/// directly as in [`VersionInfo::from_pe_reader`](crate::VersionInfo::from_pe_reader).
+/// Only the `assemblyIdentity` of the manifest itself is considered, not
+/// those of the assemblies it depends on, e.g. the common controls.
+///
+/// The manifest version is separate from the version information, which
+/// never falls back to it: neither takes precedence over the other, and
+/// Windows uses the manifest version only to bind side-by-side assemblies,
+/// whereas Explorer and installers show the version information. The two
+/// usually agree, but are often left out of sync, e.g. with the manifest
+/// version at `1.0.0.0`.
+///
+/// Returns `Ok(None)` if the file has no manifest, or its manifest has no
+/// `assemblyIdentity` with a `version` attribute or is not well-formed enough
+/// to find one. This function requires the `manifest` feature.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The file does not exist.
+/// - The file is not accessible.
+/// - The file is not a valid PE image, see [`ErrorKind::InvalidPeImage`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use win32_version_info::manifest_version;
+///
+/// let version = manifest_version("path/to/your/file.exe")
+///     .expect("Failed to read the manifest");
+///
+/// if let Some(version) = version {
+///     println!("Manifest version: {version}");
+/// }
+/// ```
+pub fn manifest_version<P: AsRef<Path>>(file_name: P) -> Result<Option<String>> {
+    let file_name = file_name.as_ref();
+    let manifest = File::open(file_name)
+        .map_err(|err| Error::new(ErrorKind::from_os(err)))
+        .and_then(|mut file| pe::read_manifest_resource(&mut file))
+        .map_err(|err| err.with_path(file_name))?;
+    Ok(manifest.and_then(|manifest| assembly_version(&decode(&manifest))))
+}
+
+/// Decodes a manifest, which is UTF-8 unless it starts with a UTF-16 byte
+/// order mark.
+fn decode(manifest: &[u8]) -> String {
+    let utf16 = |bytes: &[u8], from_bytes: fn([u8; 2]) -> u16| {
+        let units = bytes
+            .chunks_exact(2)
+            .filter_map(|unit| unit.try_into().ok().map(from_bytes))
+            .collect::<Vec<_>>();
+        String::from_utf16_lossy(&units)
+    };
+    match manifest {
+        [0xFF, 0xFE, rest @ ..] => utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => utf16(rest, u16::from_be_bytes),
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest).into_owned(),
+        _ => String::from_utf8_lossy(manifest).into_owned(),
+    }
+}
+
+/// Finds the `version` attribute of the `assemblyIdentity` element that is a
+/// child of the root `assembly` element.
+fn assembly_version(manifest: &str) -> Option<String> {
+    let mut depth = 0usize;
+    let mut rest = manifest;
+    loop {
+        rest = rest.get(rest.find('<')? + 1..)?;
+        // markup other than elements, which may contain `<` and `>`
+        let markup = [("!--", "-->"), ("![CDATA[", "]]>"), ("?", "?>"), ("!", ">")]
+            .into_iter()
+            .find_map(|(start, end)| Some((rest.strip_prefix(start)?, end)));
+        if let Some((markup, end)) = markup {
+            rest = markup.get(markup.find(end)? + end.len()..)?;
+            continue;
+        }
+        if let Some(end_tag) = rest.strip_prefix('/') {
+            rest = end_tag.get(end_tag.find('>')? + 1..)?;
+            depth = depth.checked_sub(1)?;
+            continue;
+        }
+        if !rest.starts_with(|char: char| char.is_alphabetic() || matches!(char, '_' | ':')) {
+            continue;
+        }
+        let (tag, after) = split_tag(rest)?;
+        rest = after;
+        let (name, attributes) = tag
+            .split_once(|char: char| char.is_ascii_whitespace())
+            .unwrap_or((tag, ""));
+        // elements may be qualified with any prefix, e.g. `asmv1:assemblyIdentity`
+        let local_name = name.trim_end_matches('/').rsplit(':').next().unwrap_or(name);
+        if depth == 1 && local_name == "assemblyIdentity" {
+            return attribute(attributes, "version").map(str::to_owned);
+        }
+        if !tag.ends_with('/') {
+            depth += 1;
+        }
+    }
+}
+
+/// Splits a start tag, without its leading `<`, from what follows its `>`,
+/// skipping over quoted attribute values.
+fn split_tag(text: &str) -> Option<(&str, &str)> {
+    let mut quote = None;
+    for (index, char) in text.char_indices() {
+        match (quote, char) {
+            (None, '>') => return Some((text.get(..index)?, text.get(index + 1..)?)),
+            (None, '"' | '\'') => quote = Some(char),
+            (Some(open), _) if open == char => quote = None,
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Finds the value of an attribute in the attributes of a start tag.
+fn attribute<'tag>(mut attributes: &'tag str, name: &str) -> Option<&'tag str> {
+    loop {
+        let (attribute_name, value) = attributes.split_once('=')?;
+        let value = value.trim_start();
+        let quote = value.chars().next().filter(|&char| matches!(char, '"' | '\''))?;
+        let (value, rest) = value.get(1..)?.split_once(quote)?;
+        if attribute_name.trim() == name {
+            return Some(value.trim());
+        }
+        attributes = rest;
+    }
+}
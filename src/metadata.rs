@@ -0,0 +1,73 @@
+use std::path::Path;
+
+use crate::{
+    FixedFileInfo,
+    Result,
+    Translation,
+    VersionInfo,
+    VersionInfoBlock,
+    VersionInfoBuilder,
+};
+
+/// Represents everything known about the version of a file: its string
+/// fields, its fixed version information and the translation the string
+/// fields were read from.
+///
+/// # Examples
+///
+/// ```no_run
+/// use win32_version_info::FileMetadata;
+///
+/// let metadata = FileMetadata::from_file("path/to/your/file.exe")
+///     .expect("Failed to retrieve version information");
+///
+/// println!("File description: {}", metadata.strings.file_description);
+/// if let Some(fixed) = metadata.fixed() {
+///     println!("File version: {}", fixed.file_version);
+/// }
+/// if let Some(translation) = metadata.translation {
+///     println!("Translation: {translation}");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct FileMetadata {
+    /// The string fields of the version information.
+    pub strings: VersionInfo,
+    /// The translation whose string table [`FileMetadata::strings`] was read
+    /// from, or `None` if no string table had a `FileVersion`.
+    pub translation: Option<Translation>,
+}
+
+impl FileMetadata {
+    /// Returns the fixed version information, i.e. the
+    /// [`VersionInfo::fixed_file_info`] of [`FileMetadata::strings`].
+    #[must_use]
+    pub const fn fixed(&self) -> Option<FixedFileInfo> {
+        self.strings.fixed_file_info
+    }
+
+    /// Retrieves the string fields, the fixed version information and the
+    /// selected translation of the specified file at once.
+    ///
+    /// The version information block is read from the file only once, and
+    /// the string fields are selected as in [`VersionInfo::from_file`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The file does not exist.
+    /// - The file is not accessible.
+    /// - The version information cannot be retrieved.
+    /// - The file has no version information, see
+    ///   [`ErrorKind::NoVersionInfo`](crate::ErrorKind::NoVersionInfo).
+    pub fn from_file<P: AsRef<Path>>(file_name: P) -> Result<Self> {
+        let block = VersionInfoBlock::from_file(file_name)?;
+        let (info, translation) = VersionInfoBuilder::new().read_selected(&block)?;
+        Ok(Self {
+            strings: VersionInfo::from_os(&info),
+            translation,
+        })
+    }
+}
@@ -0,0 +1,370 @@
+//! A minimal parser of PE images, just enough to locate the version resource
+//! and the manifest without going through the loader.
+//!
+//! All integers in a PE image are little-endian.
+
+use std::io::{
+    Read,
+    Seek,
+    SeekFrom,
+};
+
+use crate::{
+    Error,
+    ErrorKind,
+    Result,
+};
+
+/// The resource type of version resources, i.e. `RT_VERSION`.
+const RT_VERSION: u32 = 16;
+
+/// The resource type of application manifests, i.e. `RT_MANIFEST`.
+#[cfg(feature = "manifest")]
+const RT_MANIFEST: u32 = 24;
+
+/// The index of the resource table in the data directories, i.e.
+/// `IMAGE_DIRECTORY_ENTRY_RESOURCE`.
+const DIRECTORY_ENTRY_RESOURCE: usize = 2;
+
+/// The size of an `IMAGE_SECTION_HEADER`.
+const SECTION_HEADER_SIZE: usize = 40;
+
+/// The size of an `IMAGE_RESOURCE_DIRECTORY`, which is followed by its entries.
+const RESOURCE_DIRECTORY_SIZE: usize = 16;
+
+/// The size of an `IMAGE_RESOURCE_DIRECTORY_ENTRY`.
+const RESOURCE_ENTRY_SIZE: usize = 8;
+
+/// The flag marking a resource directory entry as a subdirectory, or its name
+/// as a string rather than an integer ID.
+const RESOURCE_HIGH_BIT: u32 = 0x8000_0000;
+
+/// The target architecture of a PE image, i.e. the `Machine` field of its
+/// COFF header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Machine {
+    /// ARM Thumb-2, i.e. `IMAGE_FILE_MACHINE_ARMNT`.
+    Arm,
+    /// ARM64, i.e. `IMAGE_FILE_MACHINE_ARM64`.
+    Arm64,
+    /// Any other architecture, with the raw value of the `Machine` field.
+    Other(u16),
+    /// x64, i.e. `IMAGE_FILE_MACHINE_AMD64`. ARM64EC images are marked as x64
+    /// as well.
+    X64,
+    /// x86, i.e. `IMAGE_FILE_MACHINE_I386`.
+    X86,
+}
+
+impl Machine {
+    /// Maps the raw value of the `Machine` field of a COFF header.
+    #[must_use]
+    pub const fn from_raw(raw: u16) -> Self {
+        match raw {
+            0x014C => Self::X86,
+            0x8664 => Self::X64,
+            0x01C4 => Self::Arm,
+            0xAA64 => Self::Arm64,
+            _ => Self::Other(raw),
+        }
+    }
+
+    /// Returns the raw value of the `Machine` field of a COFF header.
+    #[must_use]
+    pub const fn to_raw(self) -> u16 {
+        match self {
+            Self::X86 => 0x014C,
+            Self::X64 => 0x8664,
+            Self::Arm => 0x01C4,
+            Self::Arm64 => 0xAA64,
+            Self::Other(raw) => raw,
+        }
+    }
+}
+
+/// A parsed PE image borrowed from a byte slice.
+pub struct PeImage<'data> {
+    data: &'data [u8],
+    machine: Machine,
+    resource_rva: u32,
+    section_table: &'data [u8],
+}
+
+impl<'data> PeImage<'data> {
+    /// Returns the target architecture of the image.
+    pub const fn machine(&self) -> Machine {
+        self.machine
+    }
+
+    /// Parses the headers of the PE image in `data`.
+    ///
+    /// Fails with [`ErrorKind::InvalidPeImage`] if `data` does not start with the
+    /// `MZ` and `PE` signatures or its headers are truncated.
+    pub fn parse(data: &'data [u8]) -> Result<Self> {
+        Self::parse_headers(data).ok_or(Error::new(ErrorKind::InvalidPeImage))
+    }
+
+    fn parse_headers(data: &'data [u8]) -> Option<Self> {
+        if data.get(..2)? != b"MZ" {
+            return None;
+        }
+        let pe_header = read_u32(data, 0x3C)? as usize;
+        if data.get(pe_header..pe_header.checked_add(4)?)? != b"PE\0\0" {
+            return None;
+        }
+        let coff_header = pe_header + 4;
+        let machine = Machine::from_raw(read_u16(data, coff_header)?);
+        let number_of_sections = usize::from(read_u16(data, coff_header + 2)?);
+        let size_of_optional_header = usize::from(read_u16(data, coff_header + 16)?);
+
+        let optional_header = coff_header + 20;
+        let data_directories = match read_u16(data, optional_header)? {
+            // PE32
+            0x10B => optional_header + 96,
+            // PE32+
+            0x20B => optional_header + 112,
+            _ => return None,
+        };
+        let number_of_data_directories = read_u32(data, data_directories - 4)? as usize;
+        let resource_directory = data_directories + DIRECTORY_ENTRY_RESOURCE * 8;
+        // some linkers leave the RVA of an empty resource table, e.g. when
+        // stripping the `.rsrc` section, so treat it as absent as well
+        let resource_rva = if number_of_data_directories > DIRECTORY_ENTRY_RESOURCE
+            && read_u32(data, resource_directory + 4)? != 0 {
+            read_u32(data, resource_directory)?
+        } else {
+            0
+        };
+
+        let section_table = optional_header + size_of_optional_header;
+        let section_table = data.get(
+            section_table..
+            section_table + number_of_sections * SECTION_HEADER_SIZE)?;
+        Some(Self {
+            data,
+            machine,
+            resource_rva,
+            section_table,
+        })
+    }
+
+    /// Locates the resource table of the image, as its file offset and the
+    /// length from there to the end of the raw data of its section.
+    ///
+    /// Returns `Ok(None)` if the image has no resources.
+    fn resource_table(&self) -> Result<Option<(usize, usize)>> {
+        if self.resource_rva == 0 {
+            return Ok(None);
+        }
+        self.section_table
+            .chunks_exact(SECTION_HEADER_SIZE)
+            .find_map(|section| {
+                let virtual_address = read_u32(section, 12)?;
+                let size_of_raw_data = read_u32(section, 16)?;
+                let pointer_to_raw_data = read_u32(section, 20)?;
+                let offset = self.resource_rva.checked_sub(virtual_address)?;
+                (offset < size_of_raw_data)
+                    .then(|| (pointer_to_raw_data as usize).checked_add(offset as usize))
+                    .flatten()
+                    .map(|start| (start, (size_of_raw_data - offset) as usize))
+            })
+            .map(Some)
+            .ok_or(Error::new(ErrorKind::InvalidPeImage))
+    }
+
+    /// Maps an RVA to its file offset, or `None` if no section has raw data
+    /// there, including the zero-filled tail of a section whose virtual size
+    /// is larger than its raw data.
+    fn rva_to_offset(&self, rva: u32) -> Option<usize> {
+        self.section_table
+            .chunks_exact(SECTION_HEADER_SIZE)
+            .find_map(|section| {
+                let virtual_address = read_u32(section, 12)?;
+                let size_of_raw_data = read_u32(section, 16)?;
+                let pointer_to_raw_data = read_u32(section, 20)?;
+                let offset = rva.checked_sub(virtual_address)?;
+                (offset < size_of_raw_data)
+                    .then(|| (pointer_to_raw_data as usize).checked_add(offset as usize))
+                    .flatten()
+            })
+    }
+
+    fn slice_at_rva(&self, rva: u32, len: usize) -> Option<&'data [u8]> {
+        let offset = self.rva_to_offset(rva)?;
+        self.data.get(offset..offset.checked_add(len)?)
+    }
+
+    /// Locates the version resource of the image, i.e. the first resource of
+    /// type `RT_VERSION`, which holds a raw version information block.
+    ///
+    /// Returns `Ok(None)` if the image has no resources or no version
+    /// resource, and fails with [`ErrorKind::InvalidPeImage`] if the resource
+    /// table is malformed.
+    pub fn version_resource(&self) -> Result<Option<&'data [u8]>> {
+        if self.resource_rva == 0 {
+            return Ok(None);
+        }
+        // the resource table is addressed by offsets from its start, so take
+        // everything from there to the end of the image
+        let resources = self.rva_to_offset(self.resource_rva)
+            .and_then(|offset| self.data.get(offset..))
+            .ok_or(Error::new(ErrorKind::InvalidPeImage))?;
+        let Some((data_rva, data_size)) = find_resource(resources, RT_VERSION)? else {
+            return Ok(None);
+        };
+        self.slice_at_rva(data_rva, data_size)
+            .ok_or(Error::new(ErrorKind::InvalidPeImage))
+            .map(Some)
+    }
+}
+
+/// Iterates over the `(name, offset)` pairs of the entries of the resource
+/// directory at `offset` in the resource table.
+fn resource_entries(resources: &[u8], offset: usize)
+-> Option<impl Iterator<Item = (u32, u32)> + '_> {
+    let named = usize::from(read_u16(resources, offset + 12)?);
+    let ids = usize::from(read_u16(resources, offset + 14)?);
+    let entries = offset + RESOURCE_DIRECTORY_SIZE;
+    let entries = resources.get(entries..entries + (named + ids) * RESOURCE_ENTRY_SIZE)?;
+    Some(entries
+        .chunks_exact(RESOURCE_ENTRY_SIZE)
+        .filter_map(|entry| Some((read_u32(entry, 0)?, read_u32(entry, 4)?))))
+}
+
+/// Finds the first resource of the given type in the resource table, as the
+/// RVA and size of its data.
+///
+/// Follows the name and language levels of the resource tree down to the
+/// resource data, taking the first entry on each level just like
+/// `FindResourceW` does when no specific language is requested.
+fn find_resource(resources: &[u8], resource_type: u32) -> Result<Option<(u32, usize)>> {
+    let Some(names) = resource_entries(resources, 0)
+        .ok_or(Error::new(ErrorKind::InvalidPeImage))?
+        .find(|&(id, _)| id == resource_type)
+        .map(|(_, offset)| offset) else {
+        return Ok(None);
+    };
+    let find_data_entry = || {
+        let (_, languages) = resource_entries(resources, subdirectory(names)?)?.next()?;
+        let (_, data_entry) = resource_entries(resources, subdirectory(languages)?)?.next()?;
+        if data_entry & RESOURCE_HIGH_BIT != 0 {
+            return None;
+        }
+        let data_rva = read_u32(resources, data_entry as usize)?;
+        let data_size = read_u32(resources, data_entry as usize + 4)?;
+        Some((data_rva, data_size as usize))
+    };
+    find_data_entry()
+        .ok_or(Error::new(ErrorKind::InvalidPeImage))
+        .map(Some)
+}
+
+/// Reads the version resource of the PE image starting at the current
+/// position of `reader`, seeking to the headers, the resource table and the
+/// resource data in turn rather than reading the whole image.
+///
+/// Returns `Ok(None)` if the image has no resources or no version resource.
+pub fn read_version_resource<R: Read + Seek>(reader: &mut R) -> Result<Option<Vec<u8>>> {
+    read_resource(reader, RT_VERSION)
+}
+
+/// Reads the manifest of the PE image starting at the current position of
+/// `reader`, as [`read_version_resource`] does for the version resource.
+///
+/// Returns `Ok(None)` if the image has no resources or no manifest.
+#[cfg(feature = "manifest")]
+pub fn read_manifest_resource<R: Read + Seek>(reader: &mut R) -> Result<Option<Vec<u8>>> {
+    read_resource(reader, RT_MANIFEST)
+}
+
+/// Reads the target architecture of the PE image starting at the current
+/// position of `reader`, reading only its headers.
+pub fn read_machine<R: Read + Seek>(reader: &mut R) -> Result<Machine> {
+    let start = reader.stream_position().map_err(|err| Error::new(ErrorKind::Os(err)))?;
+    let headers = read_headers(reader, start)?;
+    PeImage::parse(&headers).map(|image| image.machine())
+}
+
+/// Reads the first resource of the given type of the PE image starting at the
+/// current position of `reader`.
+fn read_resource<R: Read + Seek>(reader: &mut R, resource_type: u32) -> Result<Option<Vec<u8>>> {
+    let start = reader.stream_position().map_err(|err| Error::new(ErrorKind::Os(err)))?;
+    let headers = read_headers(reader, start)?;
+
+    let image = PeImage::parse(&headers)?;
+    let Some((offset, len)) = image.resource_table()? else {
+        return Ok(None);
+    };
+    let resources = read_at(reader, start + offset as u64, len)?;
+    let Some((data_rva, data_size)) = find_resource(&resources, resource_type)? else {
+        return Ok(None);
+    };
+    let data_offset = image.rva_to_offset(data_rva)
+        .ok_or(Error::new(ErrorKind::InvalidPeImage))?;
+    read_at(reader, start + data_offset as u64, data_size).map(Some)
+}
+
+/// Reads the headers of the PE image starting at `start` in `reader`, up to
+/// the end of the section table, without reading the rest of the image.
+fn read_headers<R: Read + Seek>(reader: &mut R, start: u64) -> Result<Vec<u8>> {
+    let dos_header = read_at(reader, start, 0x40)?;
+    if dos_header.get(..2) != Some(b"MZ") {
+        return Err(Error::new(ErrorKind::InvalidPeImage));
+    }
+    // read up to the end of the COFF header, which gives the size of the
+    // optional header and the section table following it
+    let pe_header = read_u32(&dos_header, 0x3C)
+        .ok_or(Error::new(ErrorKind::InvalidPeImage))? as usize;
+    // the offset of the PE header is arbitrary, so the sums below may
+    // overflow on 32-bit targets
+    let coff_header_end = pe_header
+        .checked_add(24)
+        .ok_or(Error::new(ErrorKind::InvalidPeImage))?;
+    let coff_header = read_at(reader, start, coff_header_end)?;
+    let number_of_sections = read_u16(&coff_header, pe_header + 6)
+        .ok_or(Error::new(ErrorKind::InvalidPeImage))?;
+    let size_of_optional_header = read_u16(&coff_header, pe_header + 20)
+        .ok_or(Error::new(ErrorKind::InvalidPeImage))?;
+    let headers_end = coff_header_end
+        .checked_add(usize::from(size_of_optional_header))
+        .and_then(|end| end.checked_add(usize::from(number_of_sections) * SECTION_HEADER_SIZE))
+        .ok_or(Error::new(ErrorKind::InvalidPeImage))?;
+    read_at(reader, start, headers_end)
+}
+
+/// Reads exactly `len` bytes at `offset` from `reader`, failing with
+/// [`ErrorKind::InvalidPeImage`] if the image ends before.
+fn read_at<R: Read + Seek>(reader: &mut R, offset: u64, len: usize) -> Result<Vec<u8>> {
+    let os_error = |err| Error::new(ErrorKind::Os(err));
+    reader.seek(SeekFrom::Start(offset)).map_err(os_error)?;
+    // read incrementally rather than allocating `len` bytes upfront, as `len`
+    // comes from the image and may be bogus
+    let mut data = Vec::new();
+    reader.by_ref().take(len as u64).read_to_end(&mut data).map_err(os_error)?;
+    if data.len() == len {
+        Ok(data)
+    } else {
+        Err(Error::new(ErrorKind::InvalidPeImage))
+    }
+}
+
+/// Returns the offset of the subdirectory an entry points to, or `None` if
+/// the entry points to data instead.
+fn subdirectory(offset: u32) -> Option<usize> {
+    (offset & RESOURCE_HIGH_BIT != 0).then_some((offset & !RESOURCE_HIGH_BIT) as usize)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset.checked_add(2)?)?
+        .try_into()
+        .ok()
+        .map(u16::from_le_bytes)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset.checked_add(4)?)?
+        .try_into()
+        .ok()
+        .map(u32::from_le_bytes)
+}
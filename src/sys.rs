@@ -0,0 +1,536 @@
+//! Thin wrappers around the Win32 functions used by this crate, implemented
+//! on top of either the `windows` crate or, with the `windows-sys` feature,
+//! the raw bindings of the `windows-sys` crate.
+//!
+//! Both backends expose exactly the same functions and constants, so the rest
+//! of the crate never depends on either crate directly. Errors are reported as
+//! [`io::Error`](std::io::Error) carrying the Win32 error code.
+
+#[cfg(not(any(feature = "windows", feature = "windows-sys")))]
+compile_error!("either the `windows` or the `windows-sys` feature must be enabled");
+
+#[cfg(not(feature = "windows-sys"))]
+mod imp {
+    use std::{
+        ffi::{
+            self,
+            CStr,
+            OsString,
+        },
+        io,
+        os::windows::prelude::{
+            AsRawHandle as _,
+            BorrowedHandle,
+            OsStringExt as _,
+        },
+        ptr,
+        slice,
+    };
+
+    use super::{
+        MODULE_ANCHOR,
+        RT_VERSION,
+        VS_VERSION_INFO,
+    };
+
+    use windows::core::{
+        Error as WinError,
+        PCSTR,
+        PCWSTR,
+        PWSTR,
+    };
+
+    use windows::Win32::Foundation::{
+        self,
+        CloseHandle,
+        HANDLE,
+        HMODULE,
+        WIN32_ERROR,
+    };
+
+    use windows::Win32::Globalization::{
+        GetThreadUILanguage,
+        GetUserDefaultUILanguage,
+        LCIDToLocaleName,
+        MultiByteToWideChar,
+        CP_ACP,
+        MULTI_BYTE_TO_WIDE_CHAR_FLAGS,
+    };
+
+    use windows::Win32::Storage::FileSystem::{
+        self,
+        GetFileVersionInfoExA,
+        GetFileVersionInfoExW,
+        GetFileVersionInfoSizeExA,
+        GetFileVersionInfoSizeExW,
+        GetFinalPathNameByHandleW,
+        VerQueryValueW,
+        FILE_NAME_NORMALIZED,
+        GETFINALPATHNAMEBYHANDLE_FLAGS,
+        GET_FILE_VERSION_INFO_FLAGS,
+        VOLUME_NAME_DOS,
+    };
+
+    use windows::Win32::System::LibraryLoader::{
+        FindResourceW,
+        GetModuleHandleExW,
+        LoadResource,
+        LockResource,
+        SizeofResource,
+        GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS,
+        GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT,
+    };
+
+    use windows::Win32::System::Threading::{
+        OpenProcess,
+        QueryFullProcessImageNameW,
+        PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    pub const FILE_VER_GET_LOCALISED: u32 = FileSystem::FILE_VER_GET_LOCALISED.0;
+    pub const FILE_VER_GET_NEUTRAL: u32 = FileSystem::FILE_VER_GET_NEUTRAL.0;
+
+    pub const ERROR_ACCESS_DENIED: i32 = Foundation::ERROR_ACCESS_DENIED.0.cast_signed();
+    pub const ERROR_RESOURCE_DATA_NOT_FOUND: i32 = Foundation::ERROR_RESOURCE_DATA_NOT_FOUND.0.cast_signed();
+    pub const ERROR_RESOURCE_NAME_NOT_FOUND: i32 = Foundation::ERROR_RESOURCE_NAME_NOT_FOUND.0.cast_signed();
+    pub const ERROR_RESOURCE_TYPE_NOT_FOUND: i32 = Foundation::ERROR_RESOURCE_TYPE_NOT_FOUND.0.cast_signed();
+    pub const ERROR_SHARING_VIOLATION: i32 = Foundation::ERROR_SHARING_VIOLATION.0.cast_signed();
+    pub const ERROR_FILENAME_EXCED_RANGE: i32 = Foundation::ERROR_FILENAME_EXCED_RANGE.0.cast_signed();
+
+    /// Converts an error of the `windows` crate back to the Win32 error code
+    /// it was created from, if any.
+    fn io_error(err: &WinError) -> io::Error {
+        io::Error::from_raw_os_error(
+            WIN32_ERROR::from_error(err)
+                .map_or_else(|| err.code().0, |code| code.0.cast_signed()))
+    }
+
+    pub fn get_file_version_info_size(flags: u32, file_name: &[u16]) -> io::Result<u32> {
+        let size = unsafe {
+            GetFileVersionInfoSizeExW(
+                GET_FILE_VERSION_INFO_FLAGS(flags),
+                PCWSTR(file_name.as_ptr()),
+                &mut 0u32)
+        };
+        if size > 0 { Ok(size) } else { Err(io::Error::last_os_error()) }
+    }
+
+    /// Reads the block into `data`, resized to `size` bytes, so its
+    /// allocation is reused if large enough.
+    pub fn get_file_version_info(flags: u32, file_name: &[u16], size: u32, data: &mut Vec<u8>) -> io::Result<()> {
+        data.clear();
+        data.resize(size as usize, 0);
+        unsafe {
+            GetFileVersionInfoExW(
+                GET_FILE_VERSION_INFO_FLAGS(flags),
+                PCWSTR(file_name.as_ptr()),
+                0,
+                size,
+                data.as_mut_ptr().cast())
+        }.map_err(|err| io_error(&err))
+    }
+
+    pub fn get_file_version_info_size_ansi(flags: u32, file_name: &CStr) -> io::Result<u32> {
+        let size = unsafe {
+            GetFileVersionInfoSizeExA(
+                GET_FILE_VERSION_INFO_FLAGS(flags),
+                PCSTR(file_name.as_ptr().cast()),
+                &mut 0u32)
+        };
+        if size > 0 { Ok(size) } else { Err(io::Error::last_os_error()) }
+    }
+
+    pub fn get_file_version_info_ansi(flags: u32, file_name: &CStr, size: u32) -> io::Result<Vec<u8>> {
+        let mut data = vec![0u8; size as usize];
+        unsafe {
+            GetFileVersionInfoExA(
+                GET_FILE_VERSION_INFO_FLAGS(flags),
+                PCSTR(file_name.as_ptr().cast()),
+                0,
+                size,
+                data.as_mut_ptr().cast())
+        }.map_err(|err| io_error(&err))?;
+        Ok(data)
+    }
+
+    /// # Safety
+    ///
+    /// `block` must be a well-formed version information block, as
+    /// `VerQueryValueW` trusts the lengths stored in it.
+    pub unsafe fn ver_query_value(block: &[u8], sub_block: &[u16]) -> Option<(*const ffi::c_void, u32)> {
+        let mut ptr = ptr::null_mut::<ffi::c_void>();
+        let mut len = 0u32;
+        let found = unsafe {
+            VerQueryValueW(
+                block.as_ptr().cast(),
+                PCWSTR(sub_block.as_ptr()),
+                ptr::addr_of_mut!(ptr),
+                ptr::addr_of_mut!(len))
+        }.as_bool();
+        found.then_some((ptr.cast_const(), len))
+    }
+
+    pub fn process_image_name(pid: u32) -> io::Result<OsString> {
+        let process = unsafe {
+            OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid)
+        }.map_err(|err| io_error(&err))?;
+        // large enough for any path, including `\\?\` prefixed long paths
+        let mut len = 0x8000u32;
+        let mut path = vec![0u16; len as usize];
+        let result = unsafe {
+            QueryFullProcessImageNameW(
+                process,
+                PROCESS_NAME_WIN32,
+                PWSTR(path.as_mut_ptr()),
+                ptr::addr_of_mut!(len))
+        };
+        unsafe { CloseHandle(process) }.map_err(|err| io_error(&err))?;
+        result.map_err(|err| io_error(&err))?;
+        Ok(OsString::from_wide(&path[..len as usize]))
+    }
+
+    pub fn final_path_name(handle: BorrowedHandle<'_>) -> io::Result<OsString> {
+        // large enough for any path, including `\\?\` prefixed long paths
+        let mut path = vec![0u16; 0x8000];
+        let len = unsafe {
+            GetFinalPathNameByHandleW(
+                HANDLE(handle.as_raw_handle()),
+                &mut path,
+                GETFINALPATHNAMEBYHANDLE_FLAGS(FILE_NAME_NORMALIZED.0 | VOLUME_NAME_DOS.0))
+        };
+        if len == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // on a too small buffer, the returned length is the required one
+        path.get(..len as usize)
+            .map(OsString::from_wide)
+            .ok_or_else(|| io::Error::from_raw_os_error(ERROR_FILENAME_EXCED_RANGE))
+    }
+
+    /// Copies the `RT_VERSION` resource of a loaded module, or of the
+    /// executable of the current process if `module` is null.
+    ///
+    /// # Safety
+    ///
+    /// `module` must be null or the handle of a module loaded in the current
+    /// process, which stays loaded during the call.
+    pub unsafe fn module_version_resource(module: *mut ffi::c_void) -> io::Result<Vec<u8>> {
+        let module = HMODULE(module);
+        let resource = unsafe { FindResourceW(module, PCWSTR(VS_VERSION_INFO), PCWSTR(RT_VERSION)) };
+        if resource.is_invalid() {
+            return Err(io::Error::last_os_error());
+        }
+        let size = unsafe { SizeofResource(module, resource) };
+        let data = unsafe { LoadResource(module, resource) }.map_err(|err| io_error(&err))?;
+        let data = unsafe { LockResource(data) };
+        if data.is_null() {
+            return Err(io::Error::from_raw_os_error(ERROR_RESOURCE_DATA_NOT_FOUND));
+        }
+        // the resource is mapped with the module, so it must be copied
+        Ok(unsafe { slice::from_raw_parts(data.cast::<u8>(), size as usize) }.to_vec())
+    }
+
+    /// Returns the handle of the module containing this crate, e.g. a DLL
+    /// it is linked into, without incrementing its reference count.
+    pub fn current_module() -> io::Result<*mut ffi::c_void> {
+        let mut module = HMODULE::default();
+        unsafe {
+            GetModuleHandleExW(
+                GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS | GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT,
+                PCWSTR(ptr::addr_of!(MODULE_ANCHOR).cast()),
+                ptr::addr_of_mut!(module))
+        }.map_err(|err| io_error(&err))?;
+        Ok(module.0)
+    }
+
+    /// Queries the UI language of the current thread, falling back to that of
+    /// the user.
+    pub fn current_ui_language() -> u16 {
+        match unsafe { GetThreadUILanguage() } {
+            0 => unsafe { GetUserDefaultUILanguage() },
+            language => language,
+        }
+    }
+
+    pub fn ansi_to_wide(bytes: &[u8]) -> Vec<u16> {
+        let flags = MULTI_BYTE_TO_WIDE_CHAR_FLAGS::default();
+        let len = unsafe { MultiByteToWideChar(CP_ACP, flags, bytes, None) };
+        let mut wide = vec![0u16; usize::try_from(len).unwrap_or(0)];
+        let len = unsafe { MultiByteToWideChar(CP_ACP, flags, bytes, Some(&mut wide)) };
+        wide.truncate(usize::try_from(len).unwrap_or(0));
+        wide
+    }
+
+    pub fn lcid_to_locale_name(lcid: u32, name: &mut [u16]) -> usize {
+        let len = unsafe { LCIDToLocaleName(lcid, Some(name), 0) };
+        usize::try_from(len).unwrap_or(0)
+    }
+}
+
+#[cfg(feature = "windows-sys")]
+mod imp {
+    use std::{
+        ffi::{
+            self,
+            CStr,
+            OsString,
+        },
+        io,
+        os::windows::prelude::{
+            AsRawHandle as _,
+            BorrowedHandle,
+            OsStringExt as _,
+        },
+        ptr,
+        slice,
+    };
+
+    use super::{
+        MODULE_ANCHOR,
+        RT_VERSION,
+        VS_VERSION_INFO,
+    };
+
+    use windows_sys::Win32::Foundation::{
+        self,
+        CloseHandle,
+        FALSE,
+    };
+
+    use windows_sys::Win32::Globalization::{
+        GetThreadUILanguage,
+        GetUserDefaultUILanguage,
+        LCIDToLocaleName,
+        MultiByteToWideChar,
+        CP_ACP,
+    };
+
+    use windows_sys::Win32::Storage::FileSystem::{
+        self,
+        GetFileVersionInfoExA,
+        GetFileVersionInfoExW,
+        GetFileVersionInfoSizeExA,
+        GetFileVersionInfoSizeExW,
+        GetFinalPathNameByHandleW,
+        VerQueryValueW,
+        FILE_NAME_NORMALIZED,
+        VOLUME_NAME_DOS,
+    };
+
+    use windows_sys::Win32::System::LibraryLoader::{
+        FindResourceW,
+        GetModuleHandleExW,
+        LoadResource,
+        LockResource,
+        SizeofResource,
+        GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS,
+        GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT,
+    };
+
+    use windows_sys::Win32::System::Threading::{
+        OpenProcess,
+        QueryFullProcessImageNameW,
+        PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    pub const FILE_VER_GET_LOCALISED: u32 = FileSystem::FILE_VER_GET_LOCALISED;
+    pub const FILE_VER_GET_NEUTRAL: u32 = FileSystem::FILE_VER_GET_NEUTRAL;
+
+    pub const ERROR_ACCESS_DENIED: i32 = Foundation::ERROR_ACCESS_DENIED.cast_signed();
+    pub const ERROR_RESOURCE_DATA_NOT_FOUND: i32 = Foundation::ERROR_RESOURCE_DATA_NOT_FOUND.cast_signed();
+    pub const ERROR_RESOURCE_NAME_NOT_FOUND: i32 = Foundation::ERROR_RESOURCE_NAME_NOT_FOUND.cast_signed();
+    pub const ERROR_RESOURCE_TYPE_NOT_FOUND: i32 = Foundation::ERROR_RESOURCE_TYPE_NOT_FOUND.cast_signed();
+    pub const ERROR_SHARING_VIOLATION: i32 = Foundation::ERROR_SHARING_VIOLATION.cast_signed();
+    pub const ERROR_FILENAME_EXCED_RANGE: i32 = Foundation::ERROR_FILENAME_EXCED_RANGE.cast_signed();
+
+    pub fn get_file_version_info_size(flags: u32, file_name: &[u16]) -> io::Result<u32> {
+        let size = unsafe {
+            GetFileVersionInfoSizeExW(flags, file_name.as_ptr(), &mut 0u32)
+        };
+        if size > 0 { Ok(size) } else { Err(io::Error::last_os_error()) }
+    }
+
+    /// Reads the block into `data`, resized to `size` bytes, so its
+    /// allocation is reused if large enough.
+    pub fn get_file_version_info(flags: u32, file_name: &[u16], size: u32, data: &mut Vec<u8>) -> io::Result<()> {
+        data.clear();
+        data.resize(size as usize, 0);
+        let succeeded = unsafe {
+            GetFileVersionInfoExW(
+                flags,
+                file_name.as_ptr(),
+                0,
+                size,
+                data.as_mut_ptr().cast())
+        };
+        if succeeded == FALSE { Err(io::Error::last_os_error()) } else { Ok(()) }
+    }
+
+    pub fn get_file_version_info_size_ansi(flags: u32, file_name: &CStr) -> io::Result<u32> {
+        let size = unsafe {
+            GetFileVersionInfoSizeExA(flags, file_name.as_ptr().cast(), &mut 0u32)
+        };
+        if size > 0 { Ok(size) } else { Err(io::Error::last_os_error()) }
+    }
+
+    pub fn get_file_version_info_ansi(flags: u32, file_name: &CStr, size: u32) -> io::Result<Vec<u8>> {
+        let mut data = vec![0u8; size as usize];
+        let succeeded = unsafe {
+            GetFileVersionInfoExA(
+                flags,
+                file_name.as_ptr().cast(),
+                0,
+                size,
+                data.as_mut_ptr().cast())
+        };
+        if succeeded == FALSE { Err(io::Error::last_os_error()) } else { Ok(data) }
+    }
+
+    /// # Safety
+    ///
+    /// `block` must be a well-formed version information block, as
+    /// `VerQueryValueW` trusts the lengths stored in it.
+    pub unsafe fn ver_query_value(block: &[u8], sub_block: &[u16]) -> Option<(*const ffi::c_void, u32)> {
+        let mut ptr = ptr::null_mut::<ffi::c_void>();
+        let mut len = 0u32;
+        let found = unsafe {
+            VerQueryValueW(
+                block.as_ptr().cast(),
+                sub_block.as_ptr(),
+                ptr::addr_of_mut!(ptr),
+                ptr::addr_of_mut!(len))
+        };
+        (found != FALSE).then_some((ptr.cast_const(), len))
+    }
+
+    pub fn process_image_name(pid: u32) -> io::Result<OsString> {
+        let process = unsafe {
+            OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid)
+        };
+        if process.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+        // large enough for any path, including `\\?\` prefixed long paths
+        let mut len = 0x8000u32;
+        let mut path = vec![0u16; len as usize];
+        let succeeded = unsafe {
+            QueryFullProcessImageNameW(
+                process,
+                PROCESS_NAME_WIN32,
+                path.as_mut_ptr(),
+                ptr::addr_of_mut!(len))
+        };
+        // the error must be taken before `CloseHandle` overwrites it
+        let result = if succeeded == FALSE { Err(io::Error::last_os_error()) } else { Ok(()) };
+        let closed = unsafe { CloseHandle(process) };
+        if closed == FALSE {
+            return Err(io::Error::last_os_error());
+        }
+        result?;
+        Ok(OsString::from_wide(&path[..len as usize]))
+    }
+
+    pub fn final_path_name(handle: BorrowedHandle<'_>) -> io::Result<OsString> {
+        // large enough for any path, including `\\?\` prefixed long paths
+        let capacity = 0x8000u32;
+        let mut path = vec![0u16; capacity as usize];
+        let len = unsafe {
+            GetFinalPathNameByHandleW(
+                handle.as_raw_handle(),
+                path.as_mut_ptr(),
+                capacity,
+                FILE_NAME_NORMALIZED | VOLUME_NAME_DOS)
+        };
+        if len == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // on a too small buffer, the returned length is the required one
+        path.get(..len as usize)
+            .map(OsString::from_wide)
+            .ok_or_else(|| io::Error::from_raw_os_error(ERROR_FILENAME_EXCED_RANGE))
+    }
+
+    /// Copies the `RT_VERSION` resource of a loaded module, or of the
+    /// executable of the current process if `module` is null.
+    ///
+    /// # Safety
+    ///
+    /// `module` must be null or the handle of a module loaded in the current
+    /// process, which stays loaded during the call.
+    pub unsafe fn module_version_resource(module: *mut ffi::c_void) -> io::Result<Vec<u8>> {
+        let resource = unsafe { FindResourceW(module, VS_VERSION_INFO, RT_VERSION) };
+        if resource.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+        let size = unsafe { SizeofResource(module, resource) };
+        let data = unsafe { LoadResource(module, resource) };
+        if data.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+        let data = unsafe { LockResource(data) };
+        if data.is_null() {
+            return Err(io::Error::from_raw_os_error(ERROR_RESOURCE_DATA_NOT_FOUND));
+        }
+        // the resource is mapped with the module, so it must be copied
+        Ok(unsafe { slice::from_raw_parts(data.cast::<u8>(), size as usize) }.to_vec())
+    }
+
+    /// Returns the handle of the module containing this crate, e.g. a DLL
+    /// it is linked into, without incrementing its reference count.
+    pub fn current_module() -> io::Result<*mut ffi::c_void> {
+        let mut module = ptr::null_mut();
+        let succeeded = unsafe {
+            GetModuleHandleExW(
+                GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS | GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT,
+                ptr::addr_of!(MODULE_ANCHOR).cast(),
+                ptr::addr_of_mut!(module))
+        };
+        if succeeded == FALSE { Err(io::Error::last_os_error()) } else { Ok(module) }
+    }
+
+    /// Queries the UI language of the current thread, falling back to that of
+    /// the user.
+    pub fn current_ui_language() -> u16 {
+        match unsafe { GetThreadUILanguage() } {
+            0 => unsafe { GetUserDefaultUILanguage() },
+            language => language,
+        }
+    }
+
+    pub fn ansi_to_wide(bytes: &[u8]) -> Vec<u16> {
+        let bytes_len = i32::try_from(bytes.len()).unwrap_or(i32::MAX);
+        let len = unsafe {
+            MultiByteToWideChar(CP_ACP, 0, bytes.as_ptr(), bytes_len, ptr::null_mut(), 0)
+        };
+        let mut wide = vec![0u16; usize::try_from(len).unwrap_or(0)];
+        let len = unsafe {
+            MultiByteToWideChar(CP_ACP, 0, bytes.as_ptr(), bytes_len, wide.as_mut_ptr(), len)
+        };
+        wide.truncate(usize::try_from(len).unwrap_or(0));
+        wide
+    }
+
+    pub fn lcid_to_locale_name(lcid: u32, name: &mut [u16]) -> usize {
+        let name_len = i32::try_from(name.len()).unwrap_or(i32::MAX);
+        let len = unsafe { LCIDToLocaleName(lcid, name.as_mut_ptr(), name_len, 0) };
+        usize::try_from(len).unwrap_or(0)
+    }
+}
+
+pub use imp::*;
+
+use std::ptr;
+
+/// The integer ID of the version resource, as passed to `FindResourceW` in
+/// place of a name, i.e. `MAKEINTRESOURCEW(VS_VERSION_INFO)`.
+const VS_VERSION_INFO: *const u16 = ptr::without_provenance(1);
+
+/// The integer ID of the `RT_VERSION` resource type, i.e.
+/// `MAKEINTRESOURCEW(16)`.
+const RT_VERSION: *const u16 = ptr::without_provenance(16);
+
+/// A static whose address lies in the module containing this crate, by which
+/// `GetModuleHandleExW` finds that module.
+static MODULE_ANCHOR: u8 = 0;
@@ -0,0 +1,169 @@
+use std::{
+    error,
+    fmt,
+    str::FromStr,
+};
+
+use crate::sys;
+
+/// The maximum length of a locale name including the terminating NUL, i.e.
+/// `LOCALE_NAME_MAX_LENGTH`.
+const LOCALE_NAME_MAX_LENGTH: usize = 85;
+
+/// Represents a translation of the version information, i.e. a pair of a
+/// language ID and a code page, as listed in the `\VarFileInfo\Translation`
+/// block of a file.
+///
+/// Each translation has its own table of string fields in the
+/// `StringFileInfo` block, keyed by the 8 hexadecimal digits of the language
+/// ID followed by the code page, e.g. `040904b0` for US English in UTF-16.
+/// [`Translation`] formats to and parses from exactly this form.
+///
+/// # Examples
+///
+/// ```
+/// use win32_version_info::Translation;
+///
+/// let translation: Translation = "040904b0".parse().unwrap();
+/// assert_eq!(translation, Translation::new(0x0409, 0x04B0));
+/// assert_eq!(translation.to_string(), "040904b0");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[expect(
+    clippy::arbitrary_source_item_ordering,
+    reason = "the fields follow the `Translation` value, and the derived `Ord` compares the language first",
+)]
+pub struct Translation {
+    /// The language ID, e.g. `0x0409` for US English.
+    pub language_id: u16,
+    /// The code page, e.g. `0x04B0` (1200) for UTF-16.
+    pub code_page: u16,
+}
+
+impl Translation {
+    /// Returns the key of the string table of this translation in the
+    /// `StringFileInfo` block, as 8 uppercase hexadecimal digits, e.g.
+    /// `040904B0`, to build paths for [`VersionInfoBlock::query_raw`].
+    ///
+    /// This is the form written by resource compilers. `VerQueryValueW`
+    /// matches keys ignoring case, so the lowercase form of [`Display`] works
+    /// just as well.
+    ///
+    /// [`VersionInfoBlock::query_raw`]: crate::VersionInfoBlock::query_raw
+    /// [`Display`]: fmt::Display
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::Translation;
+    ///
+    /// let translation = Translation::new(0x0409, 0x04B0);
+    /// assert_eq!(translation.as_stringfileinfo_key(), "040904B0");
+    /// let path = format!("\\StringFileInfo\\{}\\FileVersion", translation.as_stringfileinfo_key());
+    /// assert_eq!(path, r"\StringFileInfo\040904B0\FileVersion");
+    /// ```
+    #[must_use]
+    pub fn as_stringfileinfo_key(&self) -> String {
+        format!("{:04X}{:04X}", self.language_id, self.code_page)
+    }
+
+    /// Returns the BCP 47 language tag of the language ID, e.g. `en-US` for
+    /// `0x0409`, as given by `LCIDToLocaleName`.
+    ///
+    /// Returns `None` if the language ID doesn't map to a locale known to the
+    /// system, or if it is language neutral, e.g. `0x0000`, which would
+    /// otherwise be resolved to the default locale of the user or system
+    /// rather than that of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use win32_version_info::Translation;
+    ///
+    /// assert_eq!(Translation::new(0x0409, 0x04B0).bcp47().as_deref(), Some("en-US"));
+    /// assert_eq!(Translation::new(0x0000, 0x04B0).bcp47(), None);
+    /// ```
+    #[must_use]
+    pub fn bcp47(&self) -> Option<String> {
+        // the primary language `LANG_NEUTRAL` denotes the neutral, user
+        // default and system default locales
+        if self.primary_language_id() == 0 {
+            return None;
+        }
+        locale_name(u32::from(self.language_id))
+    }
+
+    /// Creates a translation from a `DWORD` of the `\VarFileInfo\Translation`
+    /// block, which holds the language ID in its low word and the code page
+    /// in its high word.
+    ///
+    /// Like all integers in a version information block, the `DWORD` is
+    /// little-endian regardless of the target, so the bytes `09 04 b0 04`
+    /// are the language ID `0x0409` followed by the code page `0x04B0`.
+    pub(crate) const fn from_le_bytes(bytes: [u8; 4]) -> Self {
+        let [language_low, language_high, code_page_low, code_page_high] = bytes;
+        Self {
+            language_id: u16::from_le_bytes([language_low, language_high]),
+            code_page: u16::from_le_bytes([code_page_low, code_page_high]),
+        }
+    }
+
+    /// Creates a translation from its language ID and code page.
+    #[must_use]
+    pub const fn new(language_id: u16, code_page: u16) -> Self {
+        Self { language_id, code_page }
+    }
+
+    /// Returns the primary language of the language ID, i.e. its low 10 bits.
+    const fn primary_language_id(self) -> u16 {
+        self.language_id & 0x03FF
+    }
+}
+
+impl fmt::Display for Translation {
+    /// Formats the translation as the 8 lowercase hexadecimal digits used as
+    /// the key of its string table, e.g. `040904b0`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04x}{:04x}", self.language_id, self.code_page)
+    }
+}
+
+impl FromStr for Translation {
+    type Err = ParseTranslationError;
+
+    /// Parses a translation from the 8 hexadecimal digits used as the key of
+    /// its string table, e.g. `040904b0` or `040904B0`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // `from_str_radix` alone would also accept a leading `+`
+        if s.len() != 8 || !s.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(ParseTranslationError);
+        }
+        let (language_id, code_page) = s.split_at(4);
+        u16::from_str_radix(language_id, 16).ok()
+            .zip(u16::from_str_radix(code_page, 16).ok())
+            .map(|(language_id, code_page)| Self { language_id, code_page })
+            .ok_or(ParseTranslationError)
+    }
+}
+
+/// The error returned when parsing a [`Translation`] from a string fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ParseTranslationError;
+
+impl fmt::Display for ParseTranslationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid translation, expected 8 hexadecimal digits such as `040904b0`")
+    }
+}
+
+impl error::Error for ParseTranslationError {}
+
+/// Converts a locale ID to a locale name with `LCIDToLocaleName`.
+fn locale_name(lcid: u32) -> Option<String> {
+    let mut name = [0u16; LOCALE_NAME_MAX_LENGTH];
+    // the length includes the terminating NUL, and is 0 on failure
+    let len = sys::lcid_to_locale_name(lcid, &mut name).checked_sub(1)?;
+    String::from_utf16(name.get(..len)?).ok().filter(|name| !name.is_empty())
+}
@@ -0,0 +1,59 @@
+//! Reads version information of a copy of a system DLL through a cache, which
+//! must be read again once the copy is modified.
+
+#![cfg(all(windows, feature = "cache"))]
+#![allow(
+    clippy::tests_outside_test_module,
+    clippy::unwrap_used,
+    reason = "integration tests fail by panicking outside of a `#[cfg(test)]` module",
+)]
+
+use std::{
+    env,
+    fs::{
+        self,
+        File,
+    },
+    path::PathBuf,
+    process,
+    time::{
+        Duration,
+        SystemTime,
+    },
+};
+
+use win32_version_info::{
+    CachedReader,
+    VersionInfo,
+};
+
+#[test]
+fn modified() {
+    let source = PathBuf::from(env::var_os("SystemRoot").unwrap())
+        .join("System32")
+        .join("kernel32.dll");
+    let copy = env::temp_dir().join(format!("win32-version-info-cache-{}.dll", process::id()));
+    fs::copy(&source, &copy).unwrap();
+
+    let reader = CachedReader::new().capacity(1);
+    let info = reader.read(&copy).unwrap();
+    assert_eq!(reader.len(), 1);
+    assert_eq!(reader.read(&copy).unwrap(), info);
+
+    // replaces the copy with another file, modified later
+    fs::copy(env::current_exe().unwrap(), &copy).unwrap();
+    File::options()
+        .write(true)
+        .open(&copy)
+        .unwrap()
+        .set_modified(SystemTime::now() + Duration::from_mins(1))
+        .unwrap();
+    let modified = reader.read(&copy).map_err(|err| err.to_string());
+    assert_eq!(modified, VersionInfo::from_file(&copy).map_err(|err| err.to_string()));
+    assert_ne!(modified, Ok(info));
+
+    // evicts the entry of the copy, as the capacity is one
+    reader.read(&source).unwrap();
+    assert_eq!(reader.len(), 1);
+    fs::remove_file(&copy).unwrap();
+}
@@ -0,0 +1,33 @@
+//! Builders of raw version information blocks shared by the tests.
+
+#![allow(
+    clippy::integer_division,
+    clippy::integer_division_remainder_used,
+    clippy::unwrap_used,
+    reason = "the builders are only used by tests, which fail by panicking, and halve byte lengths of UTF-16 text",
+)]
+
+/// Builds a node of a version information block, padding the value and every
+/// child to 32 bits.
+pub fn node(key: &str, text: bool, value: &[u8], children: &[Vec<u8>]) -> Vec<u8> {
+    let pad = |data: &mut Vec<u8>| data.resize(data.len().next_multiple_of(4), 0);
+    let mut data = vec![0; 6];
+    data.extend(key.encode_utf16().chain(Some(0)).flat_map(u16::to_le_bytes));
+    pad(&mut data);
+    data.extend(value);
+    for child in children {
+        pad(&mut data);
+        data.extend(child);
+    }
+    let len = u16::try_from(data.len()).unwrap();
+    let value_len = u16::try_from(if text { value.len() / 2 } else { value.len() }).unwrap();
+    data[0..2].copy_from_slice(&len.to_le_bytes());
+    data[2..4].copy_from_slice(&value_len.to_le_bytes());
+    data[4..6].copy_from_slice(&u16::from(text).to_le_bytes());
+    data
+}
+
+/// Encodes a text value as NUL-terminated UTF-16.
+pub fn text(value: &str) -> Vec<u8> {
+    value.encode_utf16().chain(Some(0)).flat_map(u16::to_le_bytes).collect()
+}
@@ -0,0 +1,68 @@
+//! Reads the version resources of the same launcher built for x86 and x64,
+//! which must yield identical fields, as blocks are laid out the same way
+//! regardless of the architecture of the image they are embedded in.
+//!
+//! The fixtures are the `RT_VERSION` resources of `t32.exe` and `t64.exe` from
+//! the `distlib` launchers vendored by pip. They declare the translation
+//! `040904b0` but only have a `080904b0` string table, so the fields are
+//! queried by their full paths.
+
+#![cfg(windows)]
+#![allow(
+    clippy::tests_outside_test_module,
+    clippy::unwrap_used,
+    reason = "integration tests fail by panicking outside of a `#[cfg(test)]` module",
+)]
+
+use win32_version_info::{
+    Translation,
+    VersionInfoBlock,
+    VsFixedFileInfo,
+    STANDARD_FIELDS,
+};
+
+const X86: &[u8] = include_bytes!("fixtures/t32.bin");
+const X64: &[u8] = include_bytes!("fixtures/t64.bin");
+
+/// Queries a string field of the `080904b0` string table, without trailing
+/// NUL characters.
+fn field(block: &VersionInfoBlock, name: &str) -> Option<String> {
+    let value = block.query_raw(&format!("\\StringFileInfo\\080904b0\\{name}")).unwrap()?;
+    let units = value
+        .chunks_exact(2)
+        .filter_map(|unit| unit.try_into().ok().map(u16::from_le_bytes))
+        .collect::<Vec<_>>();
+    Some(String::from_utf16(&units).unwrap().trim_end_matches('\0').to_owned())
+}
+
+#[test]
+fn fields() {
+    let x86 = VersionInfoBlock::from_bytes(X86).unwrap();
+    let x64 = VersionInfoBlock::from_bytes(X64).unwrap();
+    for name in STANDARD_FIELDS {
+        let (x86_value, x64_value) = (field(&x86, name), field(&x64, name));
+        match *name {
+            // the only fields naming the image itself
+            "InternalName" | "OriginalFilename" => {
+                assert_eq!(x86_value.as_deref(), Some("t32.exe"), "{name}");
+                assert_eq!(x64_value.as_deref(), Some("t64.exe"), "{name}");
+            }
+            _ => assert_eq!(x86_value, x64_value, "{name}"),
+        }
+    }
+    assert_eq!(field(&x86, "FileVersion").as_deref(), Some("1.1.0.14"));
+}
+
+#[test]
+fn fixed_file_info() {
+    let x86 = VsFixedFileInfo::from_bytes(X86).unwrap();
+    assert!(x86.is_some());
+    assert_eq!(x86, VsFixedFileInfo::from_bytes(X64).unwrap());
+}
+
+#[test]
+fn translations() {
+    let x86 = VersionInfoBlock::from_bytes(X86).unwrap().translations().unwrap();
+    assert_eq!(x86, [Translation::new(0x0409, 0x04B0)]);
+    assert_eq!(x86, VersionInfoBlock::from_bytes(X64).unwrap().translations().unwrap());
+}
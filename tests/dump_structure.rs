@@ -0,0 +1,79 @@
+//! Describes the structure of version information blocks, which must list
+//! every node with the length of its value.
+
+#![cfg(windows)]
+#![allow(
+    clippy::tests_outside_test_module,
+    clippy::unwrap_used,
+    reason = "integration tests fail by panicking outside of a `#[cfg(test)]` module",
+)]
+
+mod common;
+
+use std::{
+    env,
+    path::PathBuf,
+};
+
+use common::{
+    node,
+    text,
+};
+use win32_version_info::{
+    VersionInfoBlock,
+    VersionInfoOs,
+};
+
+#[test]
+fn synthetic() {
+    let fixed_file_info = [
+        0xFEEF04BD, 0x0001_0000, 0x0001_0002, 0x0003_0004, 0x0001_0002, 0x0003_0004,
+        0x3F, 0, 0x0004_0004, 1, 0, 0, 0,
+    ]
+        .into_iter()
+        .flat_map(u32::to_le_bytes)
+        .collect::<Vec<_>>();
+    let block = node("VS_VERSION_INFO", false, &fixed_file_info, &[
+        node("StringFileInfo", true, &[], &[
+            node("040904b0", true, &[], &[
+                node("FileVersion", true, &text("1.2.3.4"), &[]),
+                node("Comments", true, &[], &[]),
+            ]),
+        ]),
+        node("VarFileInfo", true, &[], &[
+            node("Translation", false, &[0x09, 0x04, 0xB0, 0x04, 0x00, 0x00, 0xE4, 0x04], &[]),
+        ]),
+    ]);
+    let structure = VersionInfoBlock::from_bytes(&block).unwrap().dump_structure();
+    assert_eq!(structure, "\
+VS_VERSION_INFO (52 bytes, binary): VS_FIXEDFILEINFO
+  dwSignature: 0xfeef04bd
+  dwStrucVersion: 0x00010000
+  dwFileVersion: 1.2.3.4
+  dwProductVersion: 1.2.3.4
+  dwFileFlagsMask: 0x0000003f
+  dwFileFlags: 0x00000000
+  dwFileOS: 0x00040004
+  dwFileType: 0x00000001
+  dwFileSubtype: 0x00000000
+  dwFileDate: 0x0000000000000000
+  StringFileInfo (0 bytes, text)
+    040904b0 (0 bytes, text)
+      FileVersion (16 bytes, text): \"1.2.3.4\\0\"
+      Comments (0 bytes, text)
+  VarFileInfo (0 bytes, text)
+    Translation (8 bytes, binary): 040904b0 000004e4
+");
+}
+
+#[test]
+fn system_file() {
+    let kernel32 = PathBuf::from(env::var_os("SystemRoot").unwrap())
+        .join("System32")
+        .join("kernel32.dll");
+    let structure = VersionInfoOs::dump_structure(kernel32).unwrap();
+    assert!(structure.starts_with("VS_VERSION_INFO (52 bytes, binary): VS_FIXEDFILEINFO\n"), "{structure}");
+    assert!(structure.contains("\n  StringFileInfo "), "{structure}");
+    assert!(structure.contains("\n  VarFileInfo "), "{structure}");
+    assert!(!structure.contains("<malformed"), "{structure}");
+}
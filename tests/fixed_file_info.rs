@@ -0,0 +1,88 @@
+//! Reads the `VS_FIXEDFILEINFO` of version information blocks, whose offset is
+//! computed from the layout of the root node rather than by `VerQueryValueW`,
+//! and must be the same value as `VerQueryValueW` finds.
+
+#![cfg(windows)]
+#![allow(
+    clippy::tests_outside_test_module,
+    clippy::unwrap_used,
+    reason = "integration tests fail by panicking outside of a `#[cfg(test)]` module",
+)]
+
+mod common;
+
+use common::{
+    node,
+    text,
+};
+use win32_version_info::{
+    ErrorKind,
+    VersionInfoBlock,
+    VsFixedFileInfo,
+};
+
+/// The offset of the `VS_FIXEDFILEINFO`, i.e. the 6-byte header and the
+/// 32-byte `VS_VERSION_INFO` key padded to 32 bits.
+const OFFSET: usize = 40;
+
+fn block() -> Vec<u8> {
+    let fixed_file_info = [0xFEEF04BD, 0x0001_0000, 0x0001_0002, 0x0003_0004]
+        .into_iter()
+        .chain(1..=9)
+        .flat_map(u32::to_le_bytes)
+        .collect::<Vec<_>>();
+    node("VS_VERSION_INFO", false, &fixed_file_info, &[
+        node("StringFileInfo", true, &[], &[
+            node("040904b0", true, &[], &[
+                node("FileVersion", true, &text("1.2.3.4"), &[]),
+            ]),
+        ]),
+    ])
+}
+
+fn assert_agrees_with_ver_query_value(data: &[u8]) {
+    let raw = VersionInfoBlock::from_bytes(data).unwrap().query_raw("\\").unwrap().unwrap();
+    assert_eq!(data[OFFSET..OFFSET + raw.len()], raw, "not at offset {OFFSET}");
+
+    let fixed = VsFixedFileInfo::from_bytes(data).unwrap().unwrap();
+    let fields = raw
+        .chunks_exact(4)
+        .map(|field| u32::from_le_bytes(field.try_into().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(fields, [
+        fixed.signature,
+        fixed.struc_version,
+        fixed.file_version_ms,
+        fixed.file_version_ls,
+        fixed.product_version_ms,
+        fixed.product_version_ls,
+        fixed.file_flags_mask,
+        fixed.file_flags,
+        fixed.file_os,
+        fixed.file_type,
+        fixed.file_subtype,
+        fixed.file_date_ms,
+        fixed.file_date_ls,
+    ], "read differently");
+}
+
+#[test]
+fn synthetic() {
+    assert_agrees_with_ver_query_value(&block());
+}
+
+#[test]
+fn pe_resources() {
+    // the `RT_VERSION` resources of `t32.exe` and `t64.exe`, see `cross_arch.rs`
+    assert_agrees_with_ver_query_value(include_bytes!("fixtures/t32.bin"));
+    assert_agrees_with_ver_query_value(include_bytes!("fixtures/t64.bin"));
+}
+
+#[test]
+fn overrun() {
+    let mut block = block();
+    // a value length reaching past the end of the root node
+    block[2..4].copy_from_slice(&u16::MAX.to_le_bytes());
+    let err = VsFixedFileInfo::from_bytes(&block).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::MalformedBlock), "{err}");
+}
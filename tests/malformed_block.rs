@@ -0,0 +1,74 @@
+//! Reads truncated and corrupted version information blocks, which must fail
+//! with `ErrorKind::MalformedBlock` rather than read out of bounds.
+
+#![cfg(windows)]
+#![allow(
+    clippy::tests_outside_test_module,
+    clippy::unwrap_used,
+    reason = "integration tests fail by panicking outside of a `#[cfg(test)]` module",
+)]
+
+mod common;
+
+use common::{
+    node,
+    text,
+};
+use win32_version_info::{
+    ErrorKind,
+    FileVersion,
+    VersionInfoOs,
+};
+
+fn block() -> Vec<u8> {
+    let fixed_file_info = [0xFEEF04BD, 0x0001_0000, 0x0001_0002, 0x0003_0004]
+        .into_iter()
+        .chain([0; 9])
+        .flat_map(u32::to_le_bytes)
+        .collect::<Vec<_>>();
+    node("VS_VERSION_INFO", false, &fixed_file_info, &[
+        node("StringFileInfo", true, &[], &[
+            node("040904b0", true, &[], &[
+                node("FileVersion", true, &text("1.2.3.4"), &[]),
+            ]),
+        ]),
+        node("VarFileInfo", true, &[], &[
+            node("Translation", false, &[0x09, 0x04, 0xB0, 0x04], &[]),
+        ]),
+    ])
+}
+
+#[test]
+fn well_formed() {
+    let info = VersionInfoOs::from_bytes(&block()).unwrap();
+    assert_eq!(info.file_version, "1.2.3.4");
+    assert_eq!(
+        info.fixed_file_info.map(|fixed_file_info| fixed_file_info.file_version),
+        Some(FileVersion::new(1, 2, 3, 4)));
+}
+
+#[test]
+fn truncated() {
+    let block = block();
+    for len in 0..block.len() {
+        assert!(
+            VersionInfoOs::from_bytes(&block[..len])
+                .is_err_and(|err| matches!(err.kind(), ErrorKind::MalformedBlock)),
+            "truncated to {len} bytes");
+    }
+}
+
+#[test]
+fn corrupted() {
+    let block = block();
+    for index in 0..block.len() {
+        for byte in [0x00, 0x01, 0x7F, 0xFF] {
+            let mut corrupted = block.clone();
+            corrupted[index] = byte;
+            assert!(
+                VersionInfoOs::from_bytes(&corrupted)
+                    .map_or_else(|err| matches!(err.kind(), ErrorKind::MalformedBlock), |_| true),
+                "byte {index} set to {byte:#04x}");
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Reads version information from modules loaded in the test process, which
+//! must match reading their files by path.
+
+#![cfg(windows)]
+#![allow(
+    clippy::tests_outside_test_module,
+    clippy::unwrap_used,
+    reason = "integration tests fail by panicking outside of a `#[cfg(test)]` module",
+)]
+
+use std::{
+    env,
+    ffi::c_void,
+    path::PathBuf,
+};
+
+use win32_version_info::{
+    ErrorKind,
+    VersionInfo,
+    VersionInfoBlock,
+    VersionInfoOs,
+};
+
+#[link(name = "kernel32")]
+extern "system" {
+    fn GetModuleHandleW(module_name: *const u16) -> *mut c_void;
+}
+
+#[test]
+fn kernel32() {
+    let name = "kernel32.dll".encode_utf16().chain(Some(0)).collect::<Vec<_>>();
+    let module = unsafe { GetModuleHandleW(name.as_ptr()) };
+    assert!(!module.is_null());
+    let block = unsafe { VersionInfoBlock::from_module(module) }.unwrap();
+    let info = VersionInfoOs::from_block(&block).unwrap();
+
+    let file_name = PathBuf::from(env::var_os("SystemRoot").unwrap())
+        .join("System32")
+        .join("kernel32.dll");
+    let expected = VersionInfo::from_file(file_name).unwrap();
+    assert_eq!(info.fixed_file_info, expected.fixed_file_info);
+    assert_eq!(info.company_name, expected.company_name.as_str());
+}
+
+#[test]
+fn without_version_info() {
+    // the test executable, which this crate is linked into, has no version
+    // resource
+    let err = VersionInfo::from_executable_module().unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::NoVersionInfo), "{err}");
+    let err = VersionInfo::from_current_module().unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::NoVersionInfo), "{err}");
+}
@@ -0,0 +1,35 @@
+//! Reads version information of a system DLL whose version resource is split
+//! into a language-neutral part and a localized part in its MUI file, whose
+//! sizes differ.
+
+#![cfg(windows)]
+#![allow(
+    clippy::tests_outside_test_module,
+    clippy::unwrap_used,
+    reason = "integration tests fail by panicking outside of a `#[cfg(test)]` module",
+)]
+
+use std::{
+    env,
+    path::PathBuf,
+};
+
+use win32_version_info::{
+    VersionInfoBlock,
+    VersionInfoOs,
+};
+
+#[test]
+fn mui() {
+    let file_name = PathBuf::from(env::var_os("SystemRoot").unwrap())
+        .join("System32")
+        .join("shell32.dll");
+
+    let (neutral, localized) = VersionInfoOs::from_file_dual(&file_name).unwrap();
+    assert!(!neutral.file_version.is_empty());
+    assert!(!localized.file_version.is_empty());
+    assert_eq!(neutral.fixed_file_info, localized.fixed_file_info);
+
+    let block = VersionInfoBlock::from_file(&file_name).unwrap();
+    assert_eq!(VersionInfoOs::from_block(&block).unwrap(), localized);
+}
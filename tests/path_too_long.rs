@@ -0,0 +1,42 @@
+//! Reads version information from paths longer than Windows supports, which
+//! must fail with `ErrorKind::PathTooLong` rather than an OS error.
+
+#![cfg(windows)]
+#![allow(
+    clippy::tests_outside_test_module,
+    clippy::unwrap_used,
+    reason = "integration tests fail by panicking outside of a `#[cfg(test)]` module",
+)]
+
+use std::path::PathBuf;
+
+use win32_version_info::{
+    ErrorKind,
+    VersionInfo,
+    VersionInfoBlock,
+};
+
+/// A verbatim path of about 40000 UTF-16 code units, too long even with the
+/// `\\?\` prefix.
+fn path() -> PathBuf {
+    PathBuf::from(format!(r"\\?\C:\{}file.exe", r"directory\".repeat(4000)))
+}
+
+#[test]
+fn verbatim() {
+    let err = VersionInfo::from_file(path()).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::PathTooLong), "{err}");
+    assert_eq!(err.path(), Some(path().as_path()));
+
+    let err = VersionInfoBlock::from_file(path()).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::PathTooLong), "{err}");
+}
+
+#[test]
+fn forward_slashes() {
+    // forward slashes are replaced with backslashes, which doesn't shorten
+    // the path
+    let path = format!("C:/{}file.exe", "directory/".repeat(4000));
+    let err = VersionInfo::from_file(path).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::PathTooLong), "{err}");
+}
@@ -0,0 +1,171 @@
+//! Reads version information from PE images in memory, e.g. members of an
+//! archive decompressed into memory, which must locate the version resource
+//! through the raw data of the sections, and fail the same way as files
+//! without a version resource if the image has no resources.
+
+#![cfg(all(windows, feature = "pe-parser"))]
+#![allow(
+    clippy::tests_outside_test_module,
+    clippy::unwrap_used,
+    reason = "integration tests fail by panicking outside of a `#[cfg(test)]` module",
+)]
+
+mod common;
+
+use std::{
+    env,
+    fs,
+    io::Cursor,
+    process,
+};
+
+use common::{
+    node,
+    text,
+};
+use win32_version_info::{
+    pe_machine,
+    ErrorKind,
+    Machine,
+    VersionInfo,
+};
+
+/// A section of a PE image built by [`image`].
+struct Section {
+    raw_data: Vec<u8>,
+    virtual_address: u32,
+    virtual_size: u32,
+}
+
+/// Builds a PE32+ image with the given sections, whose resource table is at
+/// `resource_rva` with `resource_size` bytes.
+fn image(resource_rva: u32, resource_size: u32, sections: &[Section]) -> Vec<u8> {
+    const PE_HEADER: usize = 0x40;
+    const OPTIONAL_HEADER: usize = PE_HEADER + 24;
+    const SIZE_OF_OPTIONAL_HEADER: u16 = 112 + 16 * 8;
+    const SECTION_TABLE: usize = OPTIONAL_HEADER + SIZE_OF_OPTIONAL_HEADER as usize;
+    let mut data = vec![0; SECTION_TABLE + sections.len() * 40];
+    data[..2].copy_from_slice(b"MZ");
+    data[0x3C..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+    data[PE_HEADER..PE_HEADER + 4].copy_from_slice(b"PE\0\0");
+    // x64
+    data[PE_HEADER + 4..PE_HEADER + 6].copy_from_slice(&0x8664u16.to_le_bytes());
+    let number_of_sections = u16::try_from(sections.len()).unwrap();
+    data[PE_HEADER + 6..PE_HEADER + 8].copy_from_slice(&number_of_sections.to_le_bytes());
+    data[PE_HEADER + 20..PE_HEADER + 22].copy_from_slice(&SIZE_OF_OPTIONAL_HEADER.to_le_bytes());
+    data[OPTIONAL_HEADER..OPTIONAL_HEADER + 2].copy_from_slice(&0x20Bu16.to_le_bytes());
+    // all 16 data directories, of which the resource table is the third
+    data[OPTIONAL_HEADER + 108..OPTIONAL_HEADER + 112].copy_from_slice(&16u32.to_le_bytes());
+    let resource_directory = OPTIONAL_HEADER + 112 + 2 * 8;
+    data[resource_directory..resource_directory + 4].copy_from_slice(&resource_rva.to_le_bytes());
+    data[resource_directory + 4..resource_directory + 8].copy_from_slice(&resource_size.to_le_bytes());
+    for (index, section) in sections.iter().enumerate() {
+        // the raw data of each section directly follows the previous one
+        data.resize(data.len().next_multiple_of(0x200), 0);
+        let header = SECTION_TABLE + index * 40;
+        let size_of_raw_data = u32::try_from(section.raw_data.len()).unwrap();
+        let pointer_to_raw_data = u32::try_from(data.len()).unwrap();
+        data[header + 8..header + 12].copy_from_slice(&section.virtual_size.to_le_bytes());
+        data[header + 12..header + 16].copy_from_slice(&section.virtual_address.to_le_bytes());
+        data[header + 16..header + 20].copy_from_slice(&size_of_raw_data.to_le_bytes());
+        data[header + 20..header + 24].copy_from_slice(&pointer_to_raw_data.to_le_bytes());
+        data.extend(&section.raw_data);
+    }
+    data
+}
+
+/// Builds a resource table at `rva` holding a single version resource, which
+/// follows the table.
+fn resource_table(rva: u32) -> Vec<u8> {
+    const SUBDIRECTORY: u32 = 0x8000_0000;
+    const DATA: u32 = 0x58;
+    let block = node("VS_VERSION_INFO", false, &[], &[
+        node("StringFileInfo", true, &[], &[
+            node("040904b0", true, &[], &[
+                node("FileVersion", true, &text("1.2.3.4"), &[]),
+            ]),
+        ]),
+    ]);
+    // the type, name and language directories with a single entry each,
+    // followed by the data entry
+    let mut data = Vec::new();
+    for (id, entry) in [(16, SUBDIRECTORY | 0x18), (1, SUBDIRECTORY | 0x30), (0x0409, 0x48)] {
+        data.extend([0; 14]);
+        data.extend(1u16.to_le_bytes());
+        data.extend(u32::to_le_bytes(id));
+        data.extend(entry.to_le_bytes());
+    }
+    data.extend((rva + DATA).to_le_bytes());
+    data.extend(u32::try_from(block.len()).unwrap().to_le_bytes());
+    data.extend([0; 8]);
+    data.extend(block);
+    data
+}
+
+fn assert_no_version_info(image: &[u8]) {
+    let err = VersionInfo::from_pe_image(image).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::NoVersionInfo), "{err}");
+    let err = VersionInfo::from_pe_reader(Cursor::new(image)).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::NoVersionInfo), "{err}");
+}
+
+#[test]
+fn no_resources() {
+    assert_no_version_info(&image(0, 0, &[]));
+}
+
+#[test]
+fn empty_resource_table() {
+    // the RVA of a stripped `.rsrc` section, which no section maps
+    assert_no_version_info(&image(0x3000, 0, &[]));
+}
+
+#[test]
+fn resource_table_in_raw_data() {
+    let sections = [Section {
+        raw_data: resource_table(0x1000),
+        virtual_address: 0x1000,
+        virtual_size: 0x1000,
+    }];
+    let info = VersionInfo::from_pe_image(&image(0x1000, 0x100, &sections)).unwrap();
+    assert_eq!(info.file_version, "1.2.3.4");
+}
+
+#[test]
+fn resource_table_in_virtual_tail() {
+    // the resource table is in the zero-filled tail of the first section,
+    // which has no raw data in the file, so the raw data following it in the
+    // file, i.e. that of the second section, must not be read instead
+    let sections = [
+        Section {
+            raw_data: vec![0; 0x200],
+            virtual_address: 0x1000,
+            virtual_size: 0x2000,
+        },
+        Section {
+            raw_data: resource_table(0x1200),
+            virtual_address: 0x3000,
+            virtual_size: 0x1000,
+        },
+    ];
+    let err = VersionInfo::from_pe_image(&image(0x1200, 0x100, &sections)).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidPeImage), "{err}");
+}
+
+#[test]
+fn machine_from_headers() {
+    // the file ends with the section table, so reading the raw data of the
+    // section would fail
+    let sections = [Section {
+        raw_data: resource_table(0x1000),
+        virtual_address: 0x1000,
+        virtual_size: 0x1000,
+    }];
+    let mut data = image(0x1000, 0x100, &sections);
+    data.truncate(0x40 + 24 + 112 + 16 * 8 + 40);
+    let file_name = env::temp_dir().join(format!("win32-version-info-pe-{}.dll", process::id()));
+    fs::write(&file_name, &data).unwrap();
+    let machine = pe_machine(&file_name);
+    fs::remove_file(&file_name).unwrap();
+    assert_eq!(machine.unwrap(), Machine::X64);
+}
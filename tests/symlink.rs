@@ -0,0 +1,42 @@
+//! Reads version information through a symbolic link to a system DLL, which
+//! must be the same as that of the DLL itself.
+
+#![cfg(windows)]
+#![allow(
+    clippy::print_stderr,
+    clippy::tests_outside_test_module,
+    clippy::unwrap_used,
+    reason = "integration tests fail by panicking outside of a `#[cfg(test)]` module, and report skipped tests on stderr",
+)]
+
+use std::{
+    env,
+    fs,
+    os::windows::fs::symlink_file,
+    path::PathBuf,
+    process,
+};
+
+use win32_version_info::VersionInfoBuilder;
+
+#[test]
+fn symlink() {
+    let target = PathBuf::from(env::var_os("SystemRoot").unwrap())
+        .join("System32")
+        .join("kernel32.dll");
+    let link = env::temp_dir().join(format!("win32-version-info-{}.dll", process::id()));
+    // creating symbolic links requires either the privilege or developer mode
+    if symlink_file(&target, &link).is_err() {
+        eprintln!("skipped: insufficient privileges to create a symbolic link");
+        return;
+    }
+
+    let expected = VersionInfoBuilder::new().read(&target);
+    let followed = VersionInfoBuilder::new().read(&link);
+    let resolved = VersionInfoBuilder::new().resolve_symlinks(true).read(&link);
+    fs::remove_file(&link).unwrap();
+
+    let expected = expected.unwrap();
+    assert_eq!(followed.unwrap(), expected);
+    assert_eq!(resolved.unwrap(), expected);
+}
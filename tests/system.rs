@@ -0,0 +1,143 @@
+//! Reads version information of executables shipped with Windows, which must
+//! have the string fields and fixed version information that Microsoft puts in
+//! all of them.
+//!
+//! Files missing from the installation, e.g. Notepad on some editions of
+//! Windows Server, are skipped.
+
+#![cfg(windows)]
+#![allow(
+    clippy::print_stderr,
+    clippy::tests_outside_test_module,
+    clippy::unwrap_in_result,
+    clippy::unwrap_used,
+    reason = "integration tests fail by panicking outside of a `#[cfg(test)]` module, and report skipped tests on stderr",
+)]
+
+use std::{
+    env,
+    path::PathBuf,
+};
+
+use win32_version_info::{
+    ErrorKind,
+    FileOsBase,
+    FileOsSubsystem,
+    Translation,
+    VersionInfo,
+    VersionInfoBlock,
+    VersionInfoBuilder,
+    VersionInfoOs,
+};
+
+/// Returns the path of a file in `%SystemRoot%\System32`, or `None` if there
+/// is no such file.
+fn system_file(name: &str) -> Option<PathBuf> {
+    let file_name = PathBuf::from(env::var_os("SystemRoot").unwrap())
+        .join("System32")
+        .join(name);
+    if !file_name.is_file() {
+        eprintln!("skipped: {} not found", file_name.display());
+        return None;
+    }
+    Some(file_name)
+}
+
+/// Reads version information of a file in `%SystemRoot%\System32`, or returns
+/// `None` if there is no such file.
+fn read(name: &str) -> Option<VersionInfo> {
+    system_file(name).map(|file_name| VersionInfo::from_file(file_name).unwrap())
+}
+
+fn check(name: &str) {
+    let Some(info) = read(name) else {
+        return;
+    };
+    assert_eq!(info.company_name, "Microsoft Corporation", "{name}");
+    assert!(info.is_microsoft_signed_hint(), "{name}");
+    assert!(!info.file_description.is_empty(), "{name}");
+
+    let fixed = info.fixed_file_info.unwrap();
+    assert!(fixed.file_version.major >= 6, "{name}: {}", fixed.file_version);
+    // the revision of the string is often not bumped along with the binary one
+    let prefix = format!("{}.{}.", fixed.file_version.major, fixed.file_version.minor);
+    assert!(info.file_version.starts_with(&prefix), "{name}: {}", info.file_version);
+    assert_eq!(fixed.os().base(), FileOsBase::Nt, "{name}");
+    assert_eq!(fixed.os().subsystem(), FileOsSubsystem::Windows32, "{name}");
+}
+
+#[test]
+fn kernel32() {
+    check("kernel32.dll");
+}
+
+#[test]
+fn user32() {
+    check("user32.dll");
+}
+
+#[test]
+fn notepad() {
+    check("notepad.exe");
+}
+
+#[test]
+fn forward_slashes() {
+    let Some(expected) = read("kernel32.dll") else {
+        return;
+    };
+    let system_root = env::var("SystemRoot").unwrap().replace('\\', "/");
+    let info = VersionInfo::from_file(format!("{system_root}/System32/kernel32.dll")).unwrap();
+    assert_eq!(info, expected);
+}
+
+#[test]
+fn size() {
+    let Some(file_name) = system_file("kernel32.dll") else {
+        return;
+    };
+    let (info, size) = VersionInfo::from_file_with_size(&file_name).unwrap();
+    assert_eq!(info, VersionInfo::from_file(&file_name).unwrap());
+    assert_eq!(size, VersionInfoBlock::from_file(&file_name).unwrap().as_bytes().len());
+}
+
+#[test]
+fn fixed_file_info_of_all_translations() {
+    let Some(file_name) = system_file("kernel32.dll") else {
+        return;
+    };
+    let expected = VersionInfo::from_file(&file_name).unwrap().fixed_file_info;
+    for (translation, info) in VersionInfoOs::all_translations_fields(&file_name).unwrap() {
+        assert_eq!(info.fixed_file_info, expected, "{translation}");
+    }
+}
+
+#[test]
+fn translation_exact() {
+    let Some(file_name) = system_file("kernel32.dll") else {
+        return;
+    };
+    let info = VersionInfo::from_file(&file_name).unwrap();
+    let declared = VersionInfoBlock::from_file(&file_name).unwrap().translations().unwrap()[0];
+    assert_eq!(VersionInfo::from_file_with_translation(&file_name, declared).unwrap(), info);
+
+    // Arabic without a code page, which no system file has a string table for
+    let missing = Translation::new(0x0401, 0x0000);
+    let err = VersionInfo::from_file_with_translation(&file_name, missing).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::TranslationNotFound(translation) if *translation == missing), "{err}");
+    assert_eq!(err.path(), Some(file_name.as_path()));
+
+    // unless the fallbacks are disabled, the string fields are selected as usual
+    let fallback = VersionInfoBuilder::new().translation_exact(Some(missing)).read(&file_name).unwrap();
+    assert_eq!(fallback, info);
+}
+
+#[cfg(feature = "manifest")]
+#[test]
+fn manifest() {
+    let Some(file_name) = system_file("notepad.exe") else {
+        return;
+    };
+    let version = win32_version_info::manifest_version(file_name).unwrap().unwrap();
+    assert_eq!(version.split('.').count(), 4, "{version}");
+}
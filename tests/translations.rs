@@ -0,0 +1,179 @@
+//! Reads the translations declared by version information blocks and the
+//! string tables selected from them.
+
+#![cfg(windows)]
+#![allow(
+    clippy::non_ascii_literal,
+    clippy::tests_outside_test_module,
+    clippy::unwrap_used,
+    reason = "integration tests fail by panicking outside of a `#[cfg(test)]` module, and decode non-ASCII text",
+)]
+
+mod common;
+
+use common::{
+    node,
+    text,
+};
+use win32_version_info::{
+    Translation,
+    VersionInfoBlock,
+    VersionInfoOs,
+};
+
+/// Builds a block with a `FileVersion` in the `040904b0` and `040704b0`
+/// string tables, declaring the given raw translations.
+fn block(translations: &[u8]) -> Vec<u8> {
+    node("VS_VERSION_INFO", false, &[], &[
+        node("StringFileInfo", true, &[], &[
+            node("040704b0", true, &[], &[
+                node("FileVersion", true, &text("German"), &[]),
+            ]),
+            node("040904b0", true, &[], &[
+                node("FileVersion", true, &text("English"), &[]),
+            ]),
+        ]),
+        node("VarFileInfo", true, &[], &[
+            node("Translation", false, translations, &[]),
+        ]),
+    ])
+}
+
+#[test]
+fn declared() {
+    let block = block(&[0x07, 0x04, 0xB0, 0x04, 0x09, 0x04, 0xB0, 0x04]);
+    assert_eq!(
+        VersionInfoBlock::from_bytes(&block).unwrap().translations().unwrap(),
+        [Translation::new(0x0407, 0x04B0), Translation::new(0x0409, 0x04B0)]);
+    assert_eq!(VersionInfoOs::from_bytes(&block).unwrap().file_version, "German");
+}
+
+#[test]
+fn empty() {
+    let block = block(&[]);
+    assert_eq!(VersionInfoBlock::from_bytes(&block).unwrap().translations().unwrap(), []);
+    assert_eq!(VersionInfoOs::from_bytes(&block).unwrap().file_version, "English");
+}
+
+#[test]
+fn neutral() {
+    // the only string table is in the neutral language, which isn't declared
+    let block = node("VS_VERSION_INFO", false, &[], &[
+        node("StringFileInfo", true, &[], &[
+            node("000004b0", true, &[], &[
+                node("CompanyName", true, &text("Neutral Company"), &[]),
+                node("FileVersion", true, &text("Neutral"), &[]),
+            ]),
+        ]),
+    ]);
+    let info = VersionInfoOs::from_bytes(&block).unwrap();
+    assert_eq!(info.file_version, "Neutral");
+    assert_eq!(info.company_name, "Neutral Company");
+}
+
+#[test]
+fn unusable() {
+    // the only string table is in a translation which isn't declared, so the
+    // resource is present but has no usable string table
+    let block = node("VS_VERSION_INFO", false, &[], &[
+        node("StringFileInfo", true, &[], &[
+            node("040704b0", true, &[], &[
+                node("FileVersion", true, &text("German"), &[]),
+            ]),
+        ]),
+    ]);
+    let info = VersionInfoOs::from_bytes(&block).unwrap();
+    assert!(info.is_empty());
+    assert_eq!(info.fixed_file_info, None);
+}
+
+#[test]
+fn byte_order() {
+    // the translation is stored as a little-endian `DWORD` with the language
+    // ID in its low word, i.e. `0x04B00409`
+    let block = block(&[0x09, 0x04, 0xB0, 0x04]);
+    let translations = VersionInfoBlock::from_bytes(&block).unwrap().translations().unwrap();
+    assert_eq!(translations, [Translation::new(0x0409, 0x04B0)]);
+    assert_eq!(translations[0].to_string(), "040904b0");
+}
+
+#[test]
+fn stringfileinfo_key() {
+    let block = VersionInfoBlock::from_bytes(&block(&[0x09, 0x04, 0xB0, 0x04])).unwrap();
+    let translation = block.translations().unwrap()[0];
+    assert_eq!(translation.as_stringfileinfo_key(), "040904B0");
+    assert_eq!(Translation::new(0x0804, 0x03A8).as_stringfileinfo_key(), "080403A8");
+
+    // the uppercase key finds the lowercase string table
+    let path = format!("\\StringFileInfo\\{}\\FileVersion", translation.as_stringfileinfo_key());
+    assert_eq!(block.query_raw(&path).unwrap(), Some(text("English")));
+}
+
+#[test]
+fn fixed_file_info_independent_of_translation() {
+    // the `VS_FIXEDFILEINFO` is in the root node, outside of any string table
+    let fixed_file_info = [0xFEEF04BD, 0x0001_0000, 0x0001_0002, 0x0003_0004]
+        .into_iter()
+        .chain(1..=9)
+        .flat_map(u32::to_le_bytes)
+        .collect::<Vec<_>>();
+    let block = |translations: &[u8]| node("VS_VERSION_INFO", false, &fixed_file_info, &[
+        node("StringFileInfo", true, &[], &[
+            node("040704b0", true, &[], &[
+                node("FileVersion", true, &text("German"), &[]),
+            ]),
+            node("040904b0", true, &[], &[
+                node("FileVersion", true, &text("English"), &[]),
+            ]),
+        ]),
+        node("VarFileInfo", true, &[], &[
+            node("Translation", false, translations, &[]),
+        ]),
+    ]);
+    let german = VersionInfoOs::from_bytes(&block(&[0x07, 0x04, 0xB0, 0x04])).unwrap();
+    let english = VersionInfoOs::from_bytes(&block(&[0x09, 0x04, 0xB0, 0x04])).unwrap();
+    assert_eq!(german.file_version, "German");
+    assert_eq!(english.file_version, "English");
+    assert!(german.fixed_file_info.is_some());
+    assert_eq!(german.fixed_file_info, english.fixed_file_info);
+}
+
+/// Reads a block declaring no translation, whose only string table is the
+/// fallback with the given key, which must be decoded as UTF-16 regardless of
+/// its code page.
+fn assert_fallback_decoded(key: &str) {
+    let block = node("VS_VERSION_INFO", false, &[], &[
+        node("StringFileInfo", true, &[], &[
+            node(key, true, &[], &[
+                node("FileVersion", true, &text("1.0 (Größe ✓)"), &[]),
+            ]),
+        ]),
+    ]);
+    let info = VersionInfoOs::from_bytes(&block).unwrap();
+    assert_eq!(info.file_version, "1.0 (Größe ✓)", "{key}");
+}
+
+#[test]
+fn fallback_us_english_unicode() {
+    assert_fallback_decoded("040904b0");
+}
+
+#[test]
+fn fallback_us_english_windows_1252() {
+    assert_fallback_decoded("040904e4");
+}
+
+#[test]
+fn fallback_us_english_unknown_code_page() {
+    assert_fallback_decoded("04090000");
+}
+
+#[test]
+fn fallback_neutral_unicode() {
+    assert_fallback_decoded("000004b0");
+}
+
+#[test]
+fn fallback_neutral_windows_1252() {
+    assert_fallback_decoded("000004e4");
+}
@@ -0,0 +1,57 @@
+//! Round-trips string fields through their raw UTF-16 code units and iterates
+//! over them, which must preserve ill-formed UTF-16 data.
+
+#![cfg(windows)]
+#![allow(
+    clippy::tests_outside_test_module,
+    clippy::unwrap_used,
+    reason = "integration tests fail by panicking outside of a `#[cfg(test)]` module",
+)]
+
+use std::{
+    ffi::OsString,
+    os::windows::ffi::OsStringExt as _,
+};
+
+use win32_version_info::{
+    VersionInfoOs,
+    STANDARD_FIELDS,
+};
+
+#[test]
+fn unpaired_surrogate() {
+    // an unpaired high surrogate, which `String` cannot represent
+    let units = [0x0057, 0xD800, 0x0069];
+    let mut info = VersionInfoOs::default();
+    info.company_name = "Contoso".into();
+    info.file_description = OsString::from_wide(&units);
+
+    let map = info.to_wide_map();
+    assert_eq!(map["FileDescription"], units);
+    assert_eq!(map["Comments"], []);
+    assert_eq!(VersionInfoOs::from_wide_map(&map), info);
+}
+
+#[test]
+fn fields() {
+    let units = [0x0057, 0xD800, 0x0069];
+    let mut info = VersionInfoOs::default();
+    info.file_description = OsString::from_wide(&units);
+
+    let names = info.fields().map(|(name, _)| name).collect::<Vec<_>>();
+    assert_eq!(names, STANDARD_FIELDS);
+    let (_, value) = info.fields().find(|&(name, _)| name == "FileDescription").unwrap();
+    assert_eq!(value, info.file_description);
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn from_fields() {
+    let units = [0x0057, 0xD800, 0x0069];
+    let info = VersionInfoOs::from_fields(STANDARD_FIELDS
+        .iter()
+        .map(|&name| (name, OsString::from_wide(&units)))
+        .collect());
+    assert!(info.fields().all(|(_, value)| value == OsString::from_wide(&units)));
+    assert_eq!(info.fixed_file_info, None);
+}